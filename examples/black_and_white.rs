@@ -1,7 +1,6 @@
 #[macro_use]
 extern crate mini_gl_fb;
 
-use mini_gl_fb::BufferFormat;
 use mini_gl_fb::glutin::event_loop::EventLoop;
 use mini_gl_fb::glutin::dpi::LogicalSize;
 
@@ -13,11 +12,9 @@ fn main() {
         buffer_size: Some(LogicalSize::new(2, 2))
     }, &event_loop);
 
-    fb.change_buffer_format::<u8>(BufferFormat::R);
-    fb.use_grayscale_shader();
-
+    // One byte per pixel is shown in grayscale
     let buffer = [128u8, 255, 50, 25];
-    fb.update_buffer(&buffer);
+    fb.show(&buffer);
 
     fb.persist(&mut event_loop);
 }
@@ -1,5 +1,9 @@
+#[macro_use]
 extern crate mini_gl_fb;
 
+use mini_gl_fb::glutin::event_loop::EventLoop;
+use mini_gl_fb::glutin::dpi::LogicalSize;
+
 /// Geometry shaders allow you to procedurally generate new geometry from the vertex data.
 ///
 /// This shader takes the two triangles submitted by mini_gl_fb and turns them into a circle!
@@ -81,10 +85,10 @@ const FRAGMENT_SOURCE: &str = r"
     out vec4 frag_color;
 
     // this is the texture uploaded by calls to `update_buffer`
-    uniform sampler2D u_tex0;
+    uniform sampler2D u_buffer;
 
     void main() {
-        vec4 sample = texture(u_tex0, g_uv);
+        vec4 sample = texture(u_buffer, g_uv);
         vec4 color;
         if (sample.r == 1.0) {
             color = sample;
@@ -102,7 +106,13 @@ fn main() {
     let width = 800.0;
     let height = 600.0;
 
-    let (mut event_loop, mut fb) = mini_gl_fb::gotta_go_fast("Hello shaders!", width, height);
+    let mut event_loop = EventLoop::new();
+    let mut fb = mini_gl_fb::get_fancy(config! {
+        window_title: String::from("Hello shaders!"),
+        window_size: LogicalSize::new(width, height),
+        // smooth out the edges of the circle
+        samples: 4
+    }, &event_loop);
 
     let mut buffer = vec![[128u8, 0, 0, 255]; (width * height) as usize];
     // let's write a red line into the buffer roughly along the diagonal (misses many pixels)
@@ -113,9 +123,12 @@ fn main() {
     }
 
     // Let's keep using the default vertex shader
-    // fb.internal.use_vertex_shader(...);
-    fb.internal.fb.use_geometry_shader(GEOMETRY_SOURCE);
-    fb.internal.fb.use_fragment_shader(FRAGMENT_SOURCE);
+    // fb.use_vertex_shader(...);
+    // If a shader doesn't compile, or the stages don't fit together (say the fragment shader reads
+    // an input the geometry shader doesn't write), the error carries the driver's log. Its
+    // `Display` output is a lot easier to read than the `Debug` output `unwrap` would show.
+    fb.use_geometry_shader(GEOMETRY_SOURCE).unwrap_or_else(|err| panic!("{}", err));
+    fb.use_fragment_shader(FRAGMENT_SOURCE).unwrap_or_else(|err| panic!("{}", err));
 
     fb.update_buffer(&buffer);
 
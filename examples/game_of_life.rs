@@ -24,7 +24,7 @@ fn main() {
     }, &event_loop);
 
     fb.change_buffer_format::<u8>(BufferFormat::R);
-    fb.use_post_process_shader(POST_PROCESS);
+    fb.use_post_process_shader(POST_PROCESS).unwrap();
 
     let mut neighbors = vec![0; WIDTH * HEIGHT];
     let mut cells = vec![false; WIDTH * HEIGHT];
@@ -57,7 +57,7 @@ fn main() {
 
                 // Reschedule another update
                 wakeup.when = Instant::now() + Duration::from_millis(
-                    if input.key_is_down(VirtualKeyCode::LShift) {
+                    if input.shift_is_down() {
                         TURBO_SPEED
                     } else {
                         NORMAL_SPEED
@@ -77,22 +77,19 @@ fn main() {
 
         if input.mouse_is_down(MouseButton::Left) || input.mouse_is_down(MouseButton::Right) {
             // Mouse was pressed
-            let (x, y) = input.mouse_pos;
-            let x = x.min(WIDTH as f64 - 0.0001).max(0.0).floor() as usize;
-            let y = y.min(HEIGHT as f64 - 0.0001).max(0.0).floor() as usize;
-            cells[y * WIDTH + x] = input.mouse_is_down(MouseButton::Left);
+            let index = input.mouse_as_buffer_index_clamped((WIDTH as u32, HEIGHT as u32));
+            cells[index] = input.mouse_is_down(MouseButton::Left);
             fb.update_buffer(&cells);
             // Give the user extra time to make something pretty each time they click
-            if !input.key_is_down(VirtualKeyCode::LShift) {
+            if !input.shift_is_down() {
                 input.adjust_wakeup(update_id.unwrap(), Wakeup::after_millis(2000));
             }
         }
 
-        // TODO support right shift. Probably by querying modifiers somehow. (modifiers support)
-        if input.key_pressed(VirtualKeyCode::LShift) {
+        if input.shift_pressed() {
             // immediately update
             input.adjust_wakeup(update_id.unwrap(), Wakeup::after_millis(0));
-        } else if input.key_released(VirtualKeyCode::LShift) {
+        } else if input.shift_released() {
             // immediately stop updating
             input.adjust_wakeup(update_id.unwrap(), Wakeup::after_millis(NORMAL_SPEED));
         }
@@ -12,15 +12,14 @@ fn main() {
     let mut buffer = vec![[128u8, 0, 0, 255]; 800 * 600];
     fb.update_buffer(&buffer);
 
-    let GlutinBreakout {
-        context,
-        mut fb,
-    } = fb.glutin_breakout();
+    let mut breakout = fb.glutin_breakout();
 
     let mut mouse_down = false;
 
     event_loop.run(move |event, _, flow| {
         match event {
+            // resizes the viewport and redraws
+            _ if breakout.handle_event(&event) => {}
             Event::WindowEvent { event: WindowEvent::CloseRequested, .. } => {
                 *flow = ControlFlow::Exit;
             }
@@ -31,11 +30,8 @@ fn main() {
                     }
                 }
             }
-            Event::WindowEvent { event: WindowEvent::Resized(size), .. } => {
-                context.resize(size);
-                context.window().request_redraw();
-            }
             Event::WindowEvent { event: WindowEvent::CursorMoved { position, .. }, .. } => {
+                let GlutinBreakout { context, fb } = &mut breakout;
                 let (x, y) = position.to_logical::<f64>(context.window().scale_factor()).into();
                 println!("({}, {})", x, y);
                 let mouse_x = min(max(x, 0), 800 - 1);
@@ -53,10 +49,6 @@ fn main() {
                     mouse_down = false;
                 }
             }
-            Event::RedrawRequested(_) => {
-                fb.redraw();
-                context.swap_buffers().unwrap();
-            }
             _ => {}
         }
     });
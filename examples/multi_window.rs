@@ -5,7 +5,7 @@ extern crate glutin;
 use mini_gl_fb::glutin::event_loop::EventLoop;
 use mini_gl_fb::glutin::event::{Event, WindowEvent, MouseButton, VirtualKeyCode, KeyboardInput, ElementState};
 use mini_gl_fb::{get_fancy, GlutinBreakout};
-use mini_gl_fb::glutin::dpi::{LogicalSize, LogicalPosition};
+use mini_gl_fb::glutin::dpi::{LogicalSize, LogicalPosition, PhysicalPosition};
 use mini_gl_fb::glutin::window::{Window, WindowId, CursorIcon};
 use mini_gl_fb::glutin::event_loop::ControlFlow;
 use mini_gl_fb::glutin::platform::run_return::EventLoopExtRunReturn;
@@ -87,7 +87,7 @@ impl DrawWindow {
     /// Updates the window's buffer. Should only be done inside of RedrawRequested events; outside
     /// of them, use `request_redraw` instead.
     fn redraw(&mut self) {
-        self.breakout.fb.update_buffer(&self.buffer);
+        self.breakout.update_buffer(&self.buffer).unwrap();
         self.breakout.context.swap_buffers().unwrap();
     }
 
@@ -155,13 +155,20 @@ impl DrawWindow {
         self.plot(end);
     }
 
-    /// Creates a new `DrawWindow` for the specified event loop, using the specified background and
-    /// foreground colors.
-    pub fn new(event_loop: &EventLoop<()>, bg: [u8; 4], fg: [u8; 4]) -> Self {
+    /// Creates a new `DrawWindow` for the specified event loop at the specified position, using the
+    /// specified background and foreground colors.
+    pub fn new(
+        event_loop: &EventLoop<()>,
+        position: PhysicalPosition<i32>,
+        bg: [u8; 4],
+        fg: [u8; 4]
+    ) -> Self {
         let mut new = Self {
             breakout: get_fancy(config! {
                 resizable: true,
-                invert_y: false
+                invert_y: false,
+                window_position: Some(position),
+                cursor_icon: CursorIcon::Crosshair
             }, &event_loop).glutin_breakout(),
             buffer: vec![],
             buffer_size: LogicalSize::new(0, 0),
@@ -171,7 +178,6 @@ impl DrawWindow {
             line_start: None,
         };
         new.resize(new.window().inner_size().to_logical(new.window().scale_factor() * SCALE_FACTOR));
-        new.window().set_cursor_icon(CursorIcon::Crosshair);
         new
     }
 }
@@ -205,7 +211,6 @@ impl TrackedWindow for DrawWindow {
                 }
             }
             Event::RedrawRequested(id) if self.matches_id(id) => {
-                unsafe { self.breakout.make_current().unwrap(); }
                 self.redraw();
             }
             Event::WindowEvent {
@@ -214,7 +219,7 @@ impl TrackedWindow for DrawWindow {
                 ..
             } if self.matches_id(id) => {
                 unsafe { self.breakout.make_current().unwrap(); }
-                self.breakout.fb.resize_viewport(size.width, size.height);
+                self.breakout.resize_viewport(size.width, size.height);
                 self.resize(size.to_logical(self.window().scale_factor() * SCALE_FACTOR));
                 self.request_redraw();
             }
@@ -266,8 +271,10 @@ impl TrackedWindow for DrawWindow {
 fn main() {
     let mut event_loop = EventLoop::new();
     let mut multi_window = MultiWindow::new();
-    multi_window.add(Box::new(DrawWindow::new(&event_loop, [25u8, 33, 40, 255], [54u8, 165, 209, 255])));
-    multi_window.add(Box::new(DrawWindow::new(&event_loop, [25u8, 40, 33, 255], [54u8, 209, 82, 255])));
-    multi_window.add(Box::new(DrawWindow::new(&event_loop, [40u8, 33, 25, 255], [209u8, 82, 54, 255])));
+    // cascade the windows so they don't all open on top of each other
+    let position = |i: i32| PhysicalPosition::new(100 + i * 150, 100 + i * 100);
+    multi_window.add(Box::new(DrawWindow::new(&event_loop, position(0), [25u8, 33, 40, 255], [54u8, 165, 209, 255])));
+    multi_window.add(Box::new(DrawWindow::new(&event_loop, position(1), [25u8, 40, 33, 255], [54u8, 209, 82, 255])));
+    multi_window.add(Box::new(DrawWindow::new(&event_loop, position(2), [40u8, 33, 25, 255], [209u8, 82, 54, 255])));
     multi_window.run(&mut event_loop);
 }
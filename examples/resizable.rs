@@ -15,8 +15,8 @@ fn main() {
     }, &event_loop);
 
 
-    fb.change_buffer_format::<u8>(BufferFormat::R);
-    fb.use_grayscale_shader();
+    fb.with_buffer_format::<u8>(BufferFormat::R)
+        .with_grayscale_shader();
 
     let buffer = [128u8, 255, 50, 25];
     fb.update_buffer(&buffer);
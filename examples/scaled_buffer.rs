@@ -15,7 +15,7 @@ fn main() {
     let mut buffer = vec![[128u8, 0, 0, 255]; 4];
     buffer[3] = [255, 255, 255, 255];
 
-    fb.update_buffer(&buffer);
+    fb.show(&buffer);
 
     fb.persist(&mut event_loop);
 }
@@ -0,0 +1,44 @@
+#[macro_use]
+extern crate mini_gl_fb;
+
+use mini_gl_fb::glutin::event_loop::EventLoop;
+use mini_gl_fb::glutin::dpi::LogicalSize;
+
+const SIZE: usize = 256;
+
+fn main() {
+    let mut event_loop = EventLoop::new();
+    // There's no titlebar, so press escape to close the window
+    let mut fb = mini_gl_fb::get_fancy(config! {
+        window_title: String::from("Transparent"),
+        window_size: LogicalSize::new(512.0, 512.0),
+        buffer_size: Some(LogicalSize::new(SIZE as u32, SIZE as u32)),
+        transparent: true,
+        decorations: false,
+        background_color: [0.0, 0.0, 0.0, 0.0]
+    }, &event_loop);
+
+    // A translucent circle on a fully transparent background. Most compositors expect
+    // premultiplied alpha, so the color is scaled by the alpha.
+    let center = SIZE as f64 / 2.0;
+    let radius = center - 8.0;
+    let alpha = 0.6;
+    let mut buffer = vec![[0u8; 4]; SIZE * SIZE];
+    for (i, pixel) in buffer.iter_mut().enumerate() {
+        let x = (i % SIZE) as f64 + 0.5 - center;
+        let y = (i / SIZE) as f64 + 0.5 - center;
+        if x * x + y * y <= radius * radius {
+            let color = [54.0, 165.0, 209.0];
+            *pixel = [
+                (color[0] * alpha) as u8,
+                (color[1] * alpha) as u8,
+                (color[2] * alpha) as u8,
+                (255.0 * alpha) as u8,
+            ];
+        }
+    }
+
+    fb.update_buffer(&buffer);
+
+    fb.persist(&mut event_loop);
+}
@@ -15,9 +15,15 @@ fn main() {
 
     fb.change_buffer_format::<u8>(BufferFormat::RG);
 
-    // This should panic! We should only be providing two components but we provide 4!
+    // This is the wrong size! We should only be providing two components but we provide 4!
+    // `update_buffer` would panic here, `try_update_buffer` lets us recover instead. (`show` picks
+    // the format from the type of the pixels, so it can't get this wrong in the first place.)
     let buffer = vec![[0u8, 50, 128, 255]; 4];
-    fb.update_buffer(&buffer);
+    if let Err(err) = fb.try_update_buffer(&buffer) {
+        eprintln!("{}, falling back to a buffer of the right size", err);
+        let buffer = vec![[0u8, 50]; 4];
+        fb.update_buffer(&buffer);
+    }
 
     fb.persist(&mut event_loop);
 }
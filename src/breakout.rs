@@ -5,7 +5,9 @@ use glutin::{WindowedContext, PossiblyCurrent, ContextError};
 use crate::core::Framebuffer;
 
 use std::collections::HashMap;
-use glutin::event::{MouseButton, VirtualKeyCode, ModifiersState};
+use std::path::PathBuf;
+use glutin::dpi::PhysicalSize;
+use glutin::event::{Event, WindowEvent, MouseButton, VirtualKeyCode, ModifiersState};
 use std::time::{Instant, Duration};
 
 /// `GlutinBreakout` is useful when you are growing out of the basic input methods and synchronous
@@ -52,7 +54,7 @@ use std::time::{Instant, Duration};
 ///                         ..
 ///                     } => return false,
 ///                     WindowEvent::Resized(size) => {
-///                         self.breakout.fb.resize_viewport(size.width, size.height);
+///                         self.breakout.handle_resize(*size);
 ///                         let size = size.to_logical(self.window().scale_factor());
 ///                         self.breakout.fb.resize_buffer(size.width, size.height);
 ///                     }
@@ -128,12 +130,25 @@ pub struct GlutinBreakout {
     /// Contains the OpenGL context and its associated window. This is a
     /// [`glutin`](https://docs.rs/glutin/0.26.0/glutin/) struct; go see their documentation on
     /// [`WindowedContext`] for more information.
+    ///
+    /// Window things that [`MiniGlFb`][crate::MiniGlFb] would normally do for you go through
+    /// [`context.window()`][glutin::ContextWrapper::window], for instance
+    /// `breakout.context.window().set_title("New title")`.
     pub context: WindowedContext<PossiblyCurrent>,
     /// Contains the [`Framebuffer`] for that context. Consult its documentation for information on
     /// how to use it.
     pub fb: Framebuffer,
 }
 
+/// The same as the implementation for [`MiniGlFb`][crate::MiniGlFb]. Requires the
+/// `raw-window-handle` feature.
+#[cfg(feature = "raw-window-handle")]
+unsafe impl raw_window_handle::HasRawWindowHandle for GlutinBreakout {
+    fn raw_window_handle(&self) -> raw_window_handle::RawWindowHandle {
+        self.context.window().raw_window_handle()
+    }
+}
+
 impl GlutinBreakout {
     /// Sets the current thread's OpenGL context to the one contained in this breakout.
     ///
@@ -155,6 +170,12 @@ impl GlutinBreakout {
     /// Here is an unsafe function containing code that makes the context current, in-place. That
     /// way, you can switch contexts in one line of code, and focus on other stuff.
     ///
+    /// Forgetting to switch makes draws land in the wrong window. Debug builds log a warning when a
+    /// framebuffer is drawn while a different window's context seems to be current. That is only
+    /// reliable if contexts are switched through this method (or
+    /// [`present_all`][crate::core::present_all]); switching with glutin's `make_current` directly
+    /// works too, but may log spurious warnings.
+    ///
     /// # Usage
     ///
     /// ```
@@ -211,7 +232,126 @@ impl GlutinBreakout {
             Err(err)
         } else {
             std::ptr::write(context_ptr, result.unwrap());
+            crate::core::set_current_context(self.fb.internal.context_id);
+            Ok(())
+        }
+    }
+
+    /// Makes this breakout's context current if it isn't already, then uploads `image_data` and
+    /// redraws like [`Framebuffer::update_buffer`]. Buffers still have to be swapped afterwards.
+    ///
+    /// With several windows, this is the safe way to update one of them: calling
+    /// `fb.update_buffer` directly uploads into whichever context happens to be current, which is
+    /// often another window's.
+    ///
+    /// Returns an error if the context couldn't be made current, in which case nothing is
+    /// uploaded.
+    pub fn update_buffer<T>(&mut self, image_data: &[T]) -> Result<(), ContextError> {
+        self.ensure_current()?;
+        self.fb.update_buffer(image_data);
+        Ok(())
+    }
+
+    /// Makes this breakout's context current, unless it already is.
+    pub(crate) fn ensure_current(&mut self) -> Result<(), ContextError> {
+        if self.context.is_current() {
+            crate::core::set_current_context(self.fb.internal.context_id);
             Ok(())
+        } else {
+            unsafe { self.make_current() }
+        }
+    }
+
+    /// Resizes both the OpenGL context and the framebuffer's viewport to the new physical size of
+    /// the window. Call this on every [`WindowEvent::Resized`]; if either half is forgotten, the
+    /// buffer gets drawn stretched or into the wrong part of the window.
+    ///
+    /// Zero sizes, which some platforms report for minimized windows, are ignored.
+    ///
+    /// The buffer itself keeps its size. Call [`Framebuffer::resize_buffer`] as well if it should
+    /// follow the window.
+    pub fn handle_resize(&mut self, size: PhysicalSize<u32>) {
+        self.resize_viewport(size.width, size.height);
+    }
+
+    /// Same as [`handle_resize`][GlutinBreakout::handle_resize], but takes the width and height
+    /// separately like [`MiniGlFb::resize_viewport`][crate::MiniGlFb::resize_viewport].
+    ///
+    /// Use this rather than [`Framebuffer::resize_viewport`], which only knows about the viewport
+    /// and leaves the context at its old size.
+    pub fn resize_viewport(&mut self, width: u32, height: u32) {
+        if width == 0 || height == 0 {
+            return;
+        }
+        self.context.resize(PhysicalSize::new(width, height));
+        self.fb.resize_viewport(width, height);
+    }
+
+    /// Takes care of the events every breakout has to handle the same way, if they are for this
+    /// window:
+    ///
+    /// - [`WindowEvent::Resized`] calls [`handle_resize`][GlutinBreakout::handle_resize] and
+    ///   requests a redraw.
+    /// - [`Event::RedrawRequested`] makes the context current if needed, then redraws the buffer
+    ///   and swaps buffers. If either fails (for instance because the context was lost), the error
+    ///   is logged through the [`log`](https://docs.rs/log) crate instead of panicking.
+    /// - [`WindowEvent::CursorMoved`] updates [`Framebuffer::mouse_position`] for the shaders'
+    ///   `u_mouse`. This one is left unhandled (the method returns `false`), since you'll probably
+    ///   want to react to it as well.
+    ///
+    /// Returns `true` if the event was handled, so it can be used as a match guard in front of your
+    /// own handling:
+    ///
+    /// ```
+    /// # use mini_gl_fb::glutin::event_loop::{EventLoop, ControlFlow};
+    /// # use mini_gl_fb::glutin::event::{Event, WindowEvent};
+    /// # use mini_gl_fb::{config, get_fancy};
+    /// #
+    /// # let event_loop = EventLoop::new();
+    /// let mut breakout = get_fancy(config! { resizable: true }, &event_loop).glutin_breakout();
+    ///
+    /// event_loop.run(move |event, _, flow| {
+    ///     *flow = ControlFlow::Wait;
+    ///     match event {
+    ///         _ if breakout.handle_event(&event) => {}
+    ///         Event::WindowEvent { event: WindowEvent::CloseRequested, .. } => {
+    ///             *flow = ControlFlow::Exit;
+    ///         }
+    ///         _ => {}
+    ///     }
+    /// })
+    /// ```
+    ///
+    /// With several windows, their contexts still have to be switched before anything else that
+    /// draws; see [`make_current`][GlutinBreakout::make_current].
+    pub fn handle_event<ET>(&mut self, event: &Event<ET>) -> bool {
+        let id = self.context.window().id();
+        match event {
+            Event::WindowEvent { window_id, event: WindowEvent::Resized(size) } if *window_id == id => {
+                self.handle_resize(*size);
+                self.context.window().request_redraw();
+                true
+            }
+            Event::RedrawRequested(window_id) if *window_id == id => {
+                // a lost context is worth a log message, not a panic in the middle of the event loop
+                if let Err(err) = self.ensure_current() {
+                    log::error!("Couldn't make the context current to redraw: {}", err);
+                    return true;
+                }
+                self.fb.redraw();
+                if let Err(err) = self.context.swap_buffers() {
+                    log::error!("Couldn't swap buffers after redrawing: {}", err);
+                }
+                true
+            }
+            Event::WindowEvent { window_id, event: WindowEvent::CursorMoved { position, .. } }
+                if *window_id == id =>
+            {
+                let window_height = self.context.window().inner_size().height;
+                self.fb.mouse_position = self.fb.window_to_content(*position, window_height);
+                false
+            }
+            _ => false,
         }
     }
 }
@@ -254,13 +394,23 @@ impl Wakeup {
 pub struct BasicInput {
     /// The mouse position in buffer coordinates.
     ///
-    /// The bottom left of the window is (0, 0). Pixel centers are at multiples of (0.5, 0.5). If
-    /// you want to use this to index into your buffer, in general the following is sufficient:
+    /// With the default [`Config::invert_y`][crate::Config::invert_y], the bottom left of the
+    /// buffer is (0, 0), otherwise the top left, matching the order of the rows you upload. Pixel
+    /// centers are at odd multiples of (0.5, 0.5). See
+    /// [`Framebuffer::window_to_buffer`][crate::Framebuffer::window_to_buffer]. If
+    /// you want to use this to index into your buffer, use
+    /// [`BasicInput::mouse_as_buffer_index`], which does the following:
     ///
     /// - clamp each coordinate to the half-open range [0.0, buffer_size)
     /// - take the floor of each component
     /// - cast to usize and compute an index: `let index = y * WIDTH + x`
     pub mouse_pos: (f64, f64),
+    /// The mouse position in logical pixels relative to the top left of the window, as reported by
+    /// the windowing system. Unlike [`BasicInput::mouse_pos`] this doesn't depend on the buffer, so
+    /// use it to position things in window space, like native popups or a tooltip.
+    ///
+    /// Multiply by [`BasicInput::scale_factor`] to get physical pixels.
+    pub mouse_pos_window: (f64, f64),
     /// Stores whether a mouse button was down and is down, in that order.
     ///
     /// If a button has not been pressed yet it will not be in the map.
@@ -271,12 +421,52 @@ pub struct BasicInput {
     pub keys: HashMap<VirtualKeyCode, (bool, bool)>,
     /// The current modifier keys that are being pressed.
     pub modifiers: ModifiersState,
+    /// The text typed since your callback was last called, with the keyboard layout and modifiers
+    /// already applied (so shift + `a` gives `A`). This is cleared after every call.
+    ///
+    /// Keys that don't produce text show up as control characters, for instance backspace as
+    /// `'\u{8}'`, enter as `'\r'` and escape as `'\u{1b}'`, so filter them out with
+    /// [`char::is_control`] if you only want printable text.
+    pub text: String,
+    /// The files that were dropped onto the window since your callback was last called. This is
+    /// cleared after every call.
+    pub dropped_files: Vec<PathBuf>,
+    /// `true` while files are being dragged over the window, for instance to highlight a drop
+    /// target. Once they are dropped they show up in [`BasicInput::dropped_files`].
+    pub hovering_files: bool,
+    /// The window's current scale factor (physical pixels per logical pixel). It changes when the
+    /// window is dragged to a monitor with a different DPI, which also resizes the window and sets
+    /// [`BasicInput::resized`].
+    ///
+    /// [`BasicInput::mouse_pos`] is computed from the physical cursor position and the physical
+    /// size of the viewport, so it stays aligned with the buffer regardless of this. Use it when
+    /// you need logical sizes, for instance to keep one buffer pixel per logical pixel on resize.
+    pub scale_factor: f64,
+    /// `true` while the window has keyboard focus. This is `false` until the window gets focus for
+    /// the first time, which it usually does as soon as it opens.
+    pub focused: bool,
+    /// `true` while the window is minimized, so that you can skip expensive rendering nobody would
+    /// see. Only some platforms (like Windows) tell windows that they were minimized, by resizing
+    /// them to zero; elsewhere this stays `false`.
+    pub minimized: bool,
+    /// If this is set to `true` by your callback, it is only called when the input changes (as if
+    /// [`BasicInput::wait`] were set) while the window is [minimized][BasicInput::minimized].
+    pub wait_when_minimized: bool,
+    /// Every gamepad seen since the window was opened, in the order they were first seen. Requires
+    /// the `gilrs` feature; see the [`gamepad`][crate::gamepad] module.
+    #[cfg(feature = "gilrs")]
+    pub gamepads: Vec<crate::gamepad::GamepadState>,
     /// This is set to `true` when the window is resized outside of your callback. If you do not
     /// update the buffer in your callback, you should still draw it if this is `true`.
     pub resized: bool,
     /// If this is set to `true` by your callback, it will not be called as fast as possible, but
     /// rather only when the input changes.
     pub wait: bool,
+    /// If this is set to `true` by your callback,
+    /// [`MiniGlFb::glutin_handle_basic_input`][crate::MiniGlFb::glutin_handle_basic_input] will
+    /// return control to you without closing the window. Calling it again resumes handling input
+    /// with the same state, and this is reset to `false`.
+    pub suspend: bool,
     /// A record of all the [`Wakeup`]s that are scheduled to happen. If your callback is being
     /// called because of a wakeup, [`BasicInput::wakeup`] will be set to `Some(id)` where `id` is
     /// the unique identifier of the [`Wakeup`].
@@ -292,13 +482,94 @@ pub struct BasicInput {
     /// ignore this, as it will always be [`None`] unless you manually schedule wakeups using
     /// [`BasicInput::schedule_wakeup`].
     pub wakeup: Option<Wakeup>,
+    /// How close together two clicks have to be to count as a double click. See
+    /// [`BasicInput::mouse_double_clicked`].
+    pub double_click: DoubleClickThreshold,
+    /// The time since your callback was last called, for frame rate independent animation:
+    ///
+    /// ```
+    /// # use mini_gl_fb::BasicInput;
+    /// # let input = BasicInput::default();
+    /// # let mut x = 0.0;
+    /// let speed = 60.0; // pixels per second
+    /// x += speed * input.dt.as_secs_f64();
+    /// ```
+    ///
+    /// This is zero the first time your callback is called, and again after resuming from
+    /// [`BasicInput::suspend`].
+    pub dt: Duration,
+    /// The time since your callback was first called.
+    pub elapsed: Duration,
     // Internal variable used to keep track of what the next wakeup ID should be. Doesn't need to be
     // `pub`; `BasicInput` is already `#[non_exhaustive]`.
     _next_wakeup_id: u32,
+    // When and where each button was last pressed, if that press could still become a double click
+    _last_clicks: HashMap<MouseButton, (Instant, (f64, f64))>,
+    // Buttons that were double clicked this last frame
+    _double_clicked: Vec<MouseButton>,
+    // When the callback was first called, and when it was called last
+    _started: Option<Instant>,
+    _last_frame: Option<Instant>,
+}
+
+/// The limits for two clicks to count as a double click, used by
+/// [`BasicInput::mouse_double_clicked`]. The default is 500 milliseconds and 4 pixels, which is
+/// close to what most desktops use.
+#[non_exhaustive]
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub struct DoubleClickThreshold {
+    /// The longest time that can pass between the two presses.
+    pub time: Duration,
+    /// How far the mouse can move between the two presses, in buffer pixels (the same units as
+    /// [`BasicInput::mouse_pos`]).
+    pub distance: f64,
+}
+
+impl Default for DoubleClickThreshold {
+    fn default() -> Self {
+        DoubleClickThreshold {
+            time: Duration::from_millis(500),
+            distance: 4.0,
+        }
+    }
 }
 
 impl BasicInput {
-    // TODO: Do we want to add a `mouse_as_buffer_index` or method or something like that?
+    /// Converts [`BasicInput::mouse_pos`] into an index into a buffer of the given size (width,
+    /// height), or `None` if the mouse is outside of the buffer.
+    ///
+    /// The index is row-major (`y * width + x`) and uses the same row order as the buffer, so it
+    /// can be used directly with the buffer you pass to `update_buffer`.
+    ///
+    /// ```
+    /// # use mini_gl_fb::BasicInput;
+    /// let mut input = BasicInput::default();
+    /// input.mouse_pos = (2.5, 1.25);
+    /// assert_eq!(input.mouse_as_buffer_index((4, 4)), Some(6));
+    ///
+    /// input.mouse_pos = (4.5, 1.25);
+    /// assert_eq!(input.mouse_as_buffer_index((4, 4)), None);
+    /// assert_eq!(input.mouse_as_buffer_index_clamped((4, 4)), 7);
+    /// ```
+    pub fn mouse_as_buffer_index(&self, buffer_size: (u32, u32)) -> Option<usize> {
+        let (x, y) = self.mouse_pos;
+        let (width, height) = buffer_size;
+        if x < 0.0 || y < 0.0 || x >= width as f64 || y >= height as f64 {
+            return None;
+        }
+        Some(y as usize * width as usize + x as usize)
+    }
+
+    /// The same as [`BasicInput::mouse_as_buffer_index`], but moves the mouse position to the
+    /// nearest pixel inside the buffer instead of returning `None`, so this always returns an
+    /// index (unless the buffer is empty, in which case it returns 0).
+    pub fn mouse_as_buffer_index_clamped(&self, buffer_size: (u32, u32)) -> usize {
+        let (x, y) = self.mouse_pos;
+        let (width, height) = buffer_size;
+        let x = x.min(width as f64 - 1.0).max(0.0) as usize;
+        let y = y.min(height as f64 - 1.0).max(0.0) as usize;
+        y * width as usize + x
+    }
 
     /// If the mouse was pressed this last frame.
     pub fn mouse_pressed(&self, button: MouseButton) -> bool {
@@ -319,6 +590,52 @@ impl BasicInput {
         &(true, false) == self.mouse.get(&button).unwrap_or(&(false, false))
     }
 
+    /// If the mouse was pressed this last frame for the second time in quick succession, without
+    /// moving much in between. What counts as quick and as much is set by
+    /// [`BasicInput::double_click`].
+    ///
+    /// [`BasicInput::mouse_pressed`] is true as well whenever this is. A third click doesn't count
+    /// as another double click, but a fourth one does.
+    pub fn mouse_double_clicked(&self, button: MouseButton) -> bool {
+        self._double_clicked.contains(&button)
+    }
+
+    /// Records a press of `button` at the current mouse position for double click detection.
+    pub(crate) fn register_click(&mut self, button: MouseButton) {
+        let now = Instant::now();
+        let (x, y) = self.mouse_pos;
+        match self._last_clicks.remove(&button) {
+            Some((time, (last_x, last_y)))
+                    if now.duration_since(time) <= self.double_click.time
+                        && (x - last_x).hypot(y - last_y) <= self.double_click.distance => {
+                self._double_clicked.push(button);
+            }
+            _ => {
+                self._last_clicks.insert(button, (now, (x, y)));
+            }
+        }
+    }
+
+    /// Forgets the double clicks of the last frame.
+    pub(crate) fn clear_double_clicks(&mut self) {
+        self._double_clicked.clear();
+    }
+
+    /// Updates [`BasicInput::dt`] and [`BasicInput::elapsed`] right before the callback is called.
+    pub(crate) fn start_frame(&mut self) {
+        let now = Instant::now();
+        let started = *self._started.get_or_insert(now);
+        self.dt = self._last_frame.map_or_else(Duration::default, |last| now.duration_since(last));
+        self.elapsed = now.duration_since(started);
+        self._last_frame = Some(now);
+    }
+
+    /// Makes the next [`BasicInput::dt`] zero, for when the callback hasn't been called in a while
+    /// on purpose.
+    pub(crate) fn reset_frame_time(&mut self) {
+        self._last_frame = None;
+    }
+
     /// If the key was pressed this last frame.
     pub fn key_pressed(&self, button: VirtualKeyCode) -> bool {
         &(false, true) == self.keys.get(&button).unwrap_or(&(false, false))
@@ -338,6 +655,77 @@ impl BasicInput {
         &(true, false) == self.keys.get(&button).unwrap_or(&(false, false))
     }
 
+    /// The keys that are currently down, in no particular order.
+    ///
+    /// ```
+    /// # use mini_gl_fb::BasicInput;
+    /// use mini_gl_fb::glutin::event::VirtualKeyCode;
+    ///
+    /// let mut input = BasicInput::default();
+    /// input.keys.insert(VirtualKeyCode::A, (false, true));
+    /// input.keys.insert(VirtualKeyCode::B, (true, false));
+    ///
+    /// assert_eq!(input.keys_down().collect::<Vec<_>>(), [VirtualKeyCode::A]);
+    /// assert!(input.any_key_pressed());
+    /// ```
+    pub fn keys_down(&self) -> impl Iterator<Item = VirtualKeyCode> + '_ {
+        self.keys.iter().filter(|(_, state)| state.1).map(|(key, _)| *key)
+    }
+
+    /// The mouse buttons that are currently down, in no particular order.
+    pub fn mouse_buttons_down(&self) -> impl Iterator<Item = MouseButton> + '_ {
+        self.mouse.iter().filter(|(_, state)| state.1).map(|(button, _)| *button)
+    }
+
+    /// If any key was pressed this last frame, for "press any key to continue".
+    pub fn any_key_pressed(&self) -> bool {
+        self.keys.values().any(|state| *state == (false, true))
+    }
+
+    /// If either Ctrl key is currently held down, according to [`BasicInput::modifiers`].
+    pub fn ctrl(&self) -> bool {
+        self.modifiers.ctrl()
+    }
+
+    /// If either Shift key is currently held down, according to [`BasicInput::modifiers`].
+    pub fn shift(&self) -> bool {
+        self.modifiers.shift()
+    }
+
+    /// If either Alt key is currently held down, according to [`BasicInput::modifiers`].
+    pub fn alt(&self) -> bool {
+        self.modifiers.alt()
+    }
+
+    /// If either logo key (Windows key, Command on macOS) is currently held down, according to
+    /// [`BasicInput::modifiers`].
+    pub fn logo(&self) -> bool {
+        self.modifiers.logo()
+    }
+
+    /// If either Shift key is currently down. This checks both [`VirtualKeyCode::LShift`] and
+    /// [`VirtualKeyCode::RShift`] as well as the modifiers, so it doesn't matter which one is used.
+    pub fn shift_is_down(&self) -> bool {
+        self.shift() || self.shift_state().1
+    }
+
+    /// If a Shift key was pressed this last frame, while no Shift key was down before.
+    pub fn shift_pressed(&self) -> bool {
+        self.shift_state() == (false, true)
+    }
+
+    /// If the last Shift key that was down was released this last frame.
+    pub fn shift_released(&self) -> bool {
+        self.shift_state() == (true, false)
+    }
+
+    /// The previous and current "down" states of the two Shift keys combined.
+    fn shift_state(&self) -> (bool, bool) {
+        let left = self.keys.get(&VirtualKeyCode::LShift).unwrap_or(&(false, false));
+        let right = self.keys.get(&VirtualKeyCode::RShift).unwrap_or(&(false, false));
+        (left.0 || right.0, left.1 || right.1)
+    }
+
     /// Given an [`Instant`] in the future (or in the past, in which case it will be triggered
     /// immediately), schedules a wakeup to be triggered then. Returns the ID of the wakeup, which
     /// will be the ID of [`BasicInput::wakeup`] if your callback is getting called by the wakeup.
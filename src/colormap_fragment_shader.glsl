@@ -0,0 +1,14 @@
+#version 330 core
+
+in vec2 v_uv;
+
+out vec4 frag_color;
+
+uniform sampler2D u_buffer;
+
+// Defined by whichever colormap gets appended to this shader
+vec3 colormap(float t);
+
+void main() {
+    frag_color = vec4(colormap(clamp(texture(u_buffer, v_uv).r, 0.0, 1.0)), 1.0);
+}
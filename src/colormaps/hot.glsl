@@ -0,0 +1,4 @@
+// Black to red to yellow to white
+vec3 colormap(float t) {
+    return clamp(vec3(t * 3.0, t * 3.0 - 1.0, t * 3.0 - 2.0), 0.0, 1.0);
+}
@@ -1,4 +1,8 @@
-use glutin::dpi::LogicalSize;
+use glutin::dpi::{LogicalSize, PhysicalPosition};
+use glutin::window::{Icon, BadIcon, CursorIcon};
+use glutin::GlProfile;
+
+use crate::core::ScaleMode;
 
 /// Configuration for "advanced" use cases, when [`gotta_go_fast`][crate::gotta_go_fast] isn't doing
 /// what you need.
@@ -41,7 +45,7 @@ pub struct Config {
     pub buffer_size: Option<LogicalSize<u32>>,
     /// If this is true, the window created by mini_gl_fb will be set to resizable. This can be
     /// changed later. Please note that the buffer itself will not be automatically resized, only
-    /// the viewport.
+    /// the viewport, unless [`buffer_follows_window`][Config::buffer_follows_window] is set.
     pub resizable: bool,
     /// The title of the window that will be created.
     pub window_title: String,
@@ -52,11 +56,138 @@ pub struct Config {
     /// most screen-space coordinate systems begin from the top-left. By explicitly setting this
     /// option to `false`, you can switch to screen-space coordinates rather than OpenGL
     /// coordinates. Otherwise, you will have to invert all mouse events received from winit/glutin.
-    pub invert_y: bool
+    pub invert_y: bool,
+    /// The OpenGL version to request, as `(major, minor)`. By default this is `None`, which asks
+    /// for the latest version the platform supports.
+    ///
+    /// The built-in shaders need OpenGL 3.3, so asking for anything older will make
+    /// [`try_get_fancy`][crate::try_get_fancy] fail with
+    /// [`MglfbError::UnsupportedGlVersion`][crate::MglfbError::UnsupportedGlVersion].
+    pub gl_version: Option<(u8, u8)>,
+    /// The OpenGL profile to request. By default this is `None`, which leaves it up to the
+    /// platform. macOS only supports the core profile for anything newer than OpenGL 2.1.
+    pub gl_profile: Option<GlProfile>,
+    /// If this is true, buffer swaps wait for the display's vertical retrace, which caps the
+    /// framerate at the refresh rate. Defaults to `false`. This can be changed later with
+    /// [`MiniGlFb::set_vsync`][crate::MiniGlFb::set_vsync].
+    pub vsync: bool,
+    /// How the buffer is fitted into the window when their aspect ratios differ. By default it is
+    /// stretched; [`ScaleMode::AspectFit`] adds borders instead.
+    pub scale_mode: ScaleMode,
+    /// The RGBA color of the borders in [`ScaleMode::AspectFit`], with components from 0 to 1.
+    /// Black by default.
+    pub letterbox_color: [f32; 4],
+    /// If this is true, the window starts out borderless fullscreen on the current monitor.
+    /// Defaults to `false`. This can be changed later with
+    /// [`MiniGlFb::set_fullscreen`][crate::MiniGlFb::set_fullscreen].
+    pub fullscreen: bool,
+    /// The icon shown in the window's titlebar and taskbar entry. By default this is `None`, which
+    /// leaves it up to the platform. This can be changed later with
+    /// [`MiniGlFb::set_window_icon`][crate::MiniGlFb::set_window_icon].
+    pub window_icon: Option<WindowIcon>,
+    /// The RGBA color (components from 0 to 1) the window is filled with as soon as it is
+    /// created, and whenever it is redrawn before a buffer has been uploaded. Black by default.
+    pub background_color: [f32; 4],
+    /// Where to put the top left corner of the window (including its decorations), in physical
+    /// pixels on the desktop. By default this is `None`, which leaves it up to the platform. This
+    /// can be changed later with
+    /// [`MiniGlFb::set_outer_position`][crate::MiniGlFb::set_outer_position].
+    pub window_position: Option<PhysicalPosition<i32>>,
+    /// If this is true, the window starts out maximized. Defaults to `false`.
+    pub maximized: bool,
+    /// Whether the window has a titlebar and borders. Defaults to `true`; set it to `false` for a
+    /// borderless window. This can be changed later with
+    /// [`MiniGlFb::set_decorations`][crate::MiniGlFb::set_decorations].
+    pub decorations: bool,
+    /// If this is true, the window stays on top of other windows. Defaults to `false`. This can be
+    /// changed later with [`MiniGlFb::set_always_on_top`][crate::MiniGlFb::set_always_on_top].
+    pub always_on_top: bool,
+    /// The smallest logical size the user can resize the window to. By default this is `None`,
+    /// which leaves it up to the platform. This can be changed later with
+    /// [`MiniGlFb::set_min_window_size`][crate::MiniGlFb::set_min_window_size].
+    pub min_window_size: Option<LogicalSize<f64>>,
+    /// The largest logical size the user can resize the window to. By default this is `None`,
+    /// meaning there is no limit. This can be changed later with
+    /// [`MiniGlFb::set_max_window_size`][crate::MiniGlFb::set_max_window_size].
+    pub max_window_size: Option<LogicalSize<f64>>,
+    /// If this is true, the alpha channel of the buffer is used to blend the window with whatever
+    /// is behind it. Defaults to `false`.
+    ///
+    /// This needs a compositor (which rules out some X11 setups) and a buffer format with an alpha
+    /// channel, usually [`BufferFormat::RGBA`][crate::BufferFormat::RGBA]. Most platforms expect
+    /// the colors to be premultiplied by their alpha. You'll likely also want to make
+    /// [`background_color`][Config::background_color] transparent. See the `transparent` example.
+    pub transparent: bool,
+    /// What the cursor looks like while it's over the window. Defaults to
+    /// [`CursorIcon::Default`]. This can be changed later with
+    /// [`MiniGlFb::set_cursor_icon`][crate::MiniGlFb::set_cursor_icon].
+    pub cursor_icon: CursorIcon,
+    /// The number of samples per pixel for multisample anti-aliasing (MSAA) of the window, or 0
+    /// (the default) to turn it off. Must be 0 or a power of two, otherwise creating the window
+    /// fails with [`MglfbError::InvalidSamples`][crate::MglfbError::InvalidSamples].
+    ///
+    /// This only smooths the edges of geometry, so it's only useful with a custom vertex or
+    /// geometry shader that draws something other than the default quad (like the
+    /// `custom_shaders` example). The buffer itself looks exactly the same either way.
+    pub samples: u16,
+    /// If this is true, the window gets an sRGB framebuffer and 8-bit buffers are treated as sRGB
+    /// colors (which is what almost all images are), so that scaling, blending and gradients are
+    /// gamma correct. Defaults to `false`, which passes the bytes straight through to the window.
+    /// See [`Framebuffer::set_srgb`][crate::Framebuffer::set_srgb].
+    pub srgb: bool,
+    /// If this is `Some`, the viewport is cleared to this RGBA color (components from 0 to 1) every
+    /// time the buffer is drawn, before the buffer itself. Defaults to `None`, which draws the
+    /// buffer over whatever was there. See
+    /// [`Framebuffer::set_clear_color`][crate::Framebuffer::set_clear_color].
+    pub clear_color: Option<[f32; 4]>,
+    /// If this is true, the buffer is resized to the window's logical size whenever the window is
+    /// resized, so that one buffer pixel is always one logical pixel, and
+    /// [`buffer_size`][Config::buffer_size] is ignored. Defaults to `false`. This can be changed
+    /// later with
+    /// [`MiniGlFb::set_buffer_follows_window`][crate::MiniGlFb::set_buffer_follows_window].
+    pub buffer_follows_window: bool
+}
+
+/// A window icon, stored as raw RGBA pixel data so that you don't have to deal with glutin's icon
+/// type. See [`Config::window_icon`].
+#[non_exhaustive]
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct WindowIcon {
+    /// The pixels, 4 bytes (red, green, blue, alpha) each, row by row starting at the top left.
+    pub rgba: Vec<u8>,
+    /// The width of the icon in pixels.
+    pub width: u32,
+    /// The height of the icon in pixels.
+    pub height: u32,
+}
+
+impl WindowIcon {
+    /// Creates an icon from RGBA pixel data. `rgba` has to hold exactly `width * height * 4`
+    /// bytes; this is checked when the icon is actually set on a window.
+    pub fn from_rgba(rgba: Vec<u8>, width: u32, height: u32) -> Self {
+        WindowIcon { rgba, width, height }
+    }
+
+    pub(crate) fn to_glutin(&self) -> Result<Icon, BadIcon> {
+        Icon::from_rgba(self.rgba.clone(), self.width, self.height)
+    }
 }
 
 impl ConfigBuilder {
-    /// Builds a new [`Config`].
+    /// Builds a new [`Config`]. Fields that weren't set keep their default values.
+    ///
+    /// ```
+    /// use mini_gl_fb::{Config, ConfigBuilder};
+    ///
+    /// let config = ConfigBuilder::default()
+    ///     .decorations(false)
+    ///     .always_on_top(true)
+    ///     .build();
+    ///
+    /// assert_eq!(config.decorations, false);
+    /// assert_eq!(config.always_on_top, true);
+    /// assert_eq!(config.resizable, Config::default().resizable);
+    /// ```
     pub fn build(&self) -> Config {
         let mut config = Config::default();
 
@@ -71,7 +202,10 @@ impl ConfigBuilder {
         }
 
         // I guess this is better than implementing the entire builder by hand
-        fields!(buffer_size, resizable, window_title, window_size, invert_y);
+        fields!(buffer_size, resizable, window_title, window_size, invert_y, gl_version, gl_profile,
+            vsync, scale_mode, letterbox_color, fullscreen, window_icon, background_color,
+            window_position, maximized, decorations, always_on_top, min_window_size, max_window_size,
+            transparent, cursor_icon, samples, srgb, clear_color, buffer_follows_window);
 
         config
     }
@@ -85,7 +219,27 @@ impl Default for Config {
             // :^)
             window_title: String::from("Super Mini GL Framebufferer 3!"),
             window_size: LogicalSize::new(600.0, 480.0),
-            invert_y: true
+            invert_y: true,
+            gl_version: None,
+            gl_profile: None,
+            vsync: false,
+            scale_mode: ScaleMode::Stretch,
+            letterbox_color: [0.0, 0.0, 0.0, 1.0],
+            fullscreen: false,
+            window_icon: None,
+            background_color: [0.0, 0.0, 0.0, 1.0],
+            window_position: None,
+            maximized: false,
+            decorations: true,
+            always_on_top: false,
+            min_window_size: None,
+            max_window_size: None,
+            transparent: false,
+            cursor_icon: CursorIcon::Default,
+            samples: 0,
+            srgb: false,
+            clear_color: None,
+            buffer_follows_window: false
         }
     }
 }
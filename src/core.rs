@@ -1,51 +1,316 @@
 use crate::breakout::{GlutinBreakout, BasicInput};
+use crate::draw_buffer::DrawBuffer;
+use crate::config::{Config, WindowIcon};
+use crate::error::{MglfbError, BufferSizeError};
 
 use rustic_gl;
+use rustic_gl::error::GlError;
 
-use glutin::{ContextBuilder, WindowedContext, PossiblyCurrent};
+use glutin::{
+    ContextBuilder, Context, WindowedContext, PossiblyCurrent, ContextError, Rect, GlRequest,
+    Api
+};
 use glutin::dpi::{LogicalSize, PhysicalPosition, PhysicalSize};
 
 use gl;
 use gl::types::*;
 
 use std::mem::size_of_val;
-use glutin::window::WindowBuilder;
+use std::collections::{HashMap, BTreeMap};
+use std::ffi::{CStr, CString, c_void};
+use std::path::Path;
+use std::marker::PhantomData;
+use std::cell::Cell;
+use std::sync::atomic::{AtomicU64, Ordering};
+use glutin::window::{WindowBuilder, Fullscreen, CursorIcon};
+use glutin::monitor::MonitorHandle;
 use glutin::event_loop::{EventLoop, ControlFlow, EventLoopWindowTarget};
 use glutin::platform::run_return::EventLoopExtRunReturn;
 use glutin::event::{Event, WindowEvent, VirtualKeyCode, ElementState, KeyboardInput, StartCause};
-use std::time::Instant;
+use std::time::{Instant, Duration};
+
+/// The oldest OpenGL version the built-in shaders work with.
+pub const MIN_GL_VERSION: (u8, u8) = (3, 3);
+
+/// The name of the `sampler2D` uniform your buffer is bound to. Custom shaders have to declare
+/// `uniform sampler2D u_buffer;` to sample it.
+///
+/// The buffer is on texture unit 0, which every sampler points to unless told otherwise, so a
+/// shader with a single sampler of another name (such as the `u_tex0` older examples used) happens
+/// to sample the buffer too. Don't rely on that: it stops working as soon as you use
+/// [`Framebuffer::set_secondary_texture`].
+pub const BUFFER_SAMPLER_NAME: &str = "u_buffer";
+
+/// The texture unit the palette of [`Framebuffer::use_palette`] is bound to. Avoid it when using
+/// [`Framebuffer::set_secondary_texture`] together with a palette.
+pub const PALETTE_TEXTURE_UNIT: u32 = 15;
+
+thread_local! {
+    // The context mini_gl_fb last made current on this thread, so that drawing a framebuffer while
+    // another window's context is current can be caught. 0 means unknown.
+    static CURRENT_CONTEXT: Cell<u64> = const { Cell::new(0) };
+}
+
+static NEXT_CONTEXT_ID: AtomicU64 = AtomicU64::new(1);
+
+static NEXT_FRAMEBUFFER_ID: AtomicU64 = AtomicU64::new(1);
+
+/// How many OpenGL errors are logged after a draw before the rest are dropped.
+const MAX_REPORTED_GL_ERRORS: usize = 16;
+
+/// Records that a newly created context is now current, and returns its id.
+fn new_current_context() -> u64 {
+    let id = NEXT_CONTEXT_ID.fetch_add(1, Ordering::Relaxed);
+    set_current_context(id);
+    id
+}
+
+/// Records that the context with the given id (from [`FramebufferInternal::context_id`]) has been
+/// made current.
+pub(crate) fn set_current_context(id: u64) {
+    CURRENT_CONTEXT.with(|current| current.set(id));
+}
+
+/// The id of the context mini_gl_fb last made current on this thread, for tagging a framebuffer
+/// created right after its context.
+pub(crate) fn current_context() -> u64 {
+    CURRENT_CONTEXT.with(Cell::get)
+}
 
 /// Create a context using glutin given a configuration.
-pub fn init_glutin_context<S: ToString, ET: 'static>(
-    window_title: S,
-    window_width: f64,
-    window_height: f64,
-    resizable: bool,
+///
+/// This creates the window and context for [`get_fancy`][crate::get_fancy] and its relatives, so
+/// it uses every window and context related field of `config`; [`Config::buffer_size`] and the
+/// other buffer settings are up to [`init_framebuffer`]. `gl_version` and `gl_profile` are left up
+/// to glutin when they are `None`. A `gl_version` older than [`MIN_GL_VERSION`] is rejected with
+/// [`MglfbError::UnsupportedGlVersion`] before anything is created, and so is a
+/// [`Config::samples`] that isn't 0 or a power of two, with [`MglfbError::InvalidSamples`].
+///
+/// If `shared_with` is given, the new context shares textures, buffers and shader programs with
+/// it (see [`ContextBuilder::with_shared_lists`]).
+///
+/// Returns [`MglfbError::Creation`] with glutin's reason if the window or context can't be built,
+/// [`MglfbError::BadIcon`] if the window icon is invalid, and [`MglfbError::MakeCurrent`] if the
+/// new context can't be made current.
+pub fn init_glutin_context<ET: 'static>(
+    config: &Config,
+    shared_with: Option<&Context<PossiblyCurrent>>,
     event_loop: &EventLoopWindowTarget<ET>
-) -> WindowedContext<PossiblyCurrent> {
-    let window_size = LogicalSize::new(window_width, window_height);
+) -> Result<WindowedContext<PossiblyCurrent>, MglfbError> {
+    let gl_request = match config.gl_version {
+        Some(version) if version < MIN_GL_VERSION => {
+            return Err(MglfbError::UnsupportedGlVersion(version));
+        }
+        Some(version) => GlRequest::Specific(Api::OpenGl, version),
+        None => GlRequest::Latest,
+    };
+    // glutin panics on these instead of reporting an error
+    if config.samples != 0 && !config.samples.is_power_of_two() {
+        return Err(MglfbError::InvalidSamples(config.samples));
+    }
+    let window_icon = config.window_icon.as_ref().map(WindowIcon::to_glutin).transpose()?;
+
+    // winit can only position a window after creating it, so keep it hidden until it's in place
+    let mut window = WindowBuilder::new()
+        .with_title(config.window_title.clone())
+        .with_inner_size(config.window_size)
+        .with_resizable(config.resizable)
+        .with_window_icon(window_icon)
+        .with_maximized(config.maximized)
+        .with_decorations(config.decorations)
+        .with_always_on_top(config.always_on_top)
+        .with_transparent(config.transparent)
+        .with_visible(config.window_position.is_none());
+    if let Some(size) = config.min_window_size {
+        window = window.with_min_inner_size(size);
+    }
+    if let Some(size) = config.max_window_size {
+        window = window.with_max_inner_size(size);
+    }
 
-    let window = WindowBuilder::new()
-        .with_title(window_title.to_string())
-        .with_inner_size(window_size)
-        .with_resizable(resizable);
+    let mut builder = ContextBuilder::new()
+        .with_gl(gl_request)
+        .with_vsync(config.vsync)
+        .with_multisampling(config.samples);
+    if config.srgb {
+        builder = builder.with_srgb(true);
+    }
+    if let Some(profile) = config.gl_profile {
+        builder = builder.with_gl_profile(profile);
+    }
+    if config.transparent {
+        // the window can only be see-through if the color buffer has somewhere to keep the alpha
+        builder = builder.with_pixel_format(24, 8);
+    }
 
+    let context = match shared_with {
+        Some(other) => builder.with_shared_lists(other).build_windowed(window, event_loop)?,
+        None => builder.build_windowed(window, event_loop)?,
+    };
     let context: WindowedContext<PossiblyCurrent> = unsafe {
-        ContextBuilder::new()
-            .build_windowed(window, event_loop)
-            .unwrap()
-            .make_current()
-            .unwrap()
+        context.make_current().map_err(|(_, err)| MglfbError::MakeCurrent(err))?
+    };
+
+    if let Some(position) = config.window_position {
+        context.window().set_outer_position(position);
+        context.window().set_visible(true);
+    }
+
+    new_current_context();
+    gl::load_with(|symbol| context.get_proc_address(symbol) as *const _);
+
+    if config.samples > 0 {
+        // on by default, but some drivers disagree
+        unsafe { gl::Enable(gl::MULTISAMPLE); }
+    }
+
+    Ok(context)
+}
+
+/// A [`Framebuffer`] that draws into an offscreen image instead of a window, created by
+/// [`init_headless_framebuffer`].
+#[non_exhaustive]
+#[derive(Debug)]
+pub struct Headless {
+    /// The OpenGL context. It has to stay alive (and current on this thread) for as long as `fb` is
+    /// used.
+    pub context: Context<PossiblyCurrent>,
+    /// Draws into the offscreen image. Use [`Framebuffer::read_pixels`] to get the result.
+    pub fb: Framebuffer,
+}
+
+impl Drop for Headless {
+    fn drop(&mut self) {
+        // the context is still alive here, since fields are dropped after this. If another context
+        // is current, leave the image to be freed with the context rather than deleting whatever
+        // the other one has under the same names
+        if !self.context.is_current() {
+            return;
+        }
+        let internal = &mut self.fb.internal;
+        unsafe {
+            gl::DeleteFramebuffers(1, &internal.target_framebuffer);
+            gl::DeleteRenderbuffers(1, &internal.target_renderbuffer);
+        }
+        internal.target_framebuffer = 0;
+        internal.target_renderbuffer = 0;
+    }
+}
+
+/// Creates a [`Framebuffer`] that renders into a `width` by `height` offscreen image, without
+/// opening a window. This is useful for tests and for rendering images in a pipeline, for example
+/// thumbnails:
+///
+/// ```rust
+/// use mini_gl_fb::core::init_headless_framebuffer;
+/// use mini_gl_fb::glutin::event_loop::EventLoop;
+///
+/// let event_loop = EventLoop::new();
+/// let mut headless = init_headless_framebuffer(2, 2, &event_loop).unwrap();
+/// headless.fb.update_buffer(&[[255u8, 0, 0, 255]; 4]);
+/// assert_eq!(&headless.fb.read_pixels(false)[0..4], &[255, 0, 0, 255]);
+/// ```
+///
+/// The buffer and the image have the same size and the buffer uses the default settings (RGBA
+/// bytes, inverted Y), but everything can be changed like for a window. Glutin still needs an
+/// event loop to find the platform's OpenGL implementation, so a display server has to be
+/// available.
+pub fn init_headless_framebuffer<ET: 'static>(
+    width: u32,
+    height: u32,
+    event_loop: &EventLoopWindowTarget<ET>
+) -> Result<Headless, MglfbError> {
+    let context = ContextBuilder::new()
+        .with_gl(GlRequest::Latest)
+        .build_headless(event_loop, PhysicalSize::new(width, height))?;
+    let context = unsafe {
+        context.make_current().map_err(|(_, err)| MglfbError::MakeCurrent(err))?
     };
 
+    let context_id = new_current_context();
     gl::load_with(|symbol| context.get_proc_address(symbol) as *const _);
 
-    context
+    // Headless contexts may not have a default framebuffer at all, so draw into our own
+    let (target, renderbuffer) = unsafe {
+        let mut renderbuffer = 0;
+        gl::GenRenderbuffers(1, &mut renderbuffer);
+        gl::BindRenderbuffer(gl::RENDERBUFFER, renderbuffer);
+        gl::RenderbufferStorage(gl::RENDERBUFFER, gl::RGBA8, width as _, height as _);
+        gl::BindRenderbuffer(gl::RENDERBUFFER, 0);
+
+        let mut framebuffer = 0;
+        gl::GenFramebuffers(1, &mut framebuffer);
+        gl::BindFramebuffer(gl::FRAMEBUFFER, framebuffer);
+        gl::FramebufferRenderbuffer(
+            gl::FRAMEBUFFER,
+            gl::COLOR_ATTACHMENT0,
+            gl::RENDERBUFFER,
+            renderbuffer,
+        );
+        (framebuffer, renderbuffer)
+    };
+
+    let mut fb = init_framebuffer(width, height, width, height, true);
+    fb.internal.target_framebuffer = target;
+    fb.internal.target_renderbuffer = renderbuffer;
+    fb.internal.context_id = context_id;
+
+    Ok(Headless { context, fb })
 }
 
 type VertexFormat = buffer_layout!([f32; 2], [f32; 2]);
 
+/// Loads the OpenGL functions through `loader`, then creates a framebuffer like
+/// [`init_framebuffer`]. For embedding mini_gl_fb in an application that creates and manages its
+/// own context, whether through glutin or something else entirely.
+///
+/// mini_gl_fb calls OpenGL through the [`gl`] crate, whose functions have to be loaded once for
+/// the context before anything can be drawn. [`init_glutin_context`] does that for the contexts it
+/// creates; for a context of your own, pass the same function you load your own bindings with
+/// (usually the platform's `GetProcAddress`). The context has to be current.
+///
+/// If the application already uses the same version of the `gl` crate and has loaded it, the
+/// functions are already there and loading them again is wasted work; call `init_framebuffer`
+/// directly instead, as described under [embedding][init_framebuffer#embedding]. Like there, the
+/// framebuffer isn't tied to a context mini_gl_fb knows about.
+pub fn init_framebuffer_with_loader<F: FnMut(&str) -> *const c_void>(
+    buffer_width: u32,
+    buffer_height: u32,
+    viewport_width: u32,
+    viewport_height: u32,
+    invert_y: bool,
+    loader: F
+) -> Framebuffer {
+    gl::load_with(loader);
+    init_framebuffer(buffer_width, buffer_height, viewport_width, viewport_height, invert_y)
+}
+
 /// Create the OpenGL resources needed for drawing to a buffer.
+///
+/// This expects a current context with the OpenGL functions already loaded into the [`gl`] crate,
+/// and doesn't load them itself. See [`init_framebuffer_with_loader`] if they aren't yet.
+///
+/// # Embedding
+///
+/// This is the way to draw a buffer into a context that mini_gl_fb didn't create, for instance
+/// inside a larger engine that loads OpenGL itself. Nothing is loaded and no window or context is
+/// touched: make the context current, make sure the `gl` crate's functions are loaded, call this
+/// and draw with the returned framebuffer. Making the context current before each use and
+/// swapping buffers stay up to you. Since mini_gl_fb doesn't know the context, the framebuffer's
+/// [`context_id`][FramebufferInternal::context_id] is 0 and debug builds don't check that it is
+/// current.
+///
+/// ```rust,no_run
+/// use mini_gl_fb::core::init_framebuffer;
+/// # fn engine_get_proc_address(_: &str) -> *const std::ffi::c_void { std::ptr::null() }
+///
+/// // done once by the engine, which uses the same `gl` crate
+/// mini_gl_fb::gl::load_with(engine_get_proc_address);
+///
+/// let mut fb = init_framebuffer(320, 240, 640, 480, true);
+/// fb.update_buffer(&vec![[0u8, 0, 0, 255]; 320 * 240]);
+/// // ...and the engine swaps buffers
+/// ```
 pub fn init_framebuffer(
     buffer_width: u32,
     buffer_height: u32,
@@ -73,15 +338,12 @@ pub fn init_framebuffer(
         build_program(&[
             Some(vertex_shader),
             Some(fragment_shader),
-        ])
+        ]).unwrap()
     };
 
-    let sampler_location = unsafe {
-        let location = gl::GetUniformLocation(program, b"u_buffer\0".as_ptr() as *const _);
-        gl::UseProgram(program);
-        gl::Uniform1i(location, 0);
-        gl::UseProgram(0);
-        location
+    let sampler_location = unsafe { buffer_sampler_location(program) };
+    let (resolution_location, time_location, mouse_location) = unsafe {
+        builtin_uniform_locations(program)
     };
 
     let texture_format = (BufferFormat::RGBA, gl::UNSIGNED_BYTE);
@@ -129,11 +391,28 @@ pub fn init_framebuffer(
     Framebuffer {
         buffer_size: LogicalSize::new(buffer_width, buffer_height),
         vp_size: PhysicalSize::new(vp_width, vp_height),
+        vp_position: PhysicalPosition::new(0, 0),
+        mouse_position: PhysicalPosition::new(0.0, 0.0),
         did_draw: false,
         inverted_y: invert_y,
+        scale_mode: ScaleMode::Stretch,
+        letterbox_color: [0.0, 0.0, 0.0, 1.0],
+        background_color: [0.0, 0.0, 0.0, 1.0],
+        blend_mode: BlendMode::Replace,
+        alpha_mode: AlphaMode::Straight,
+        clear_color: None,
         internal: FramebufferInternal {
             program,
+            linked_program: program,
+            programs: Vec::new(),
             sampler_location,
+            resolution_location,
+            time_location,
+            mouse_location,
+            start_time: Instant::now(),
+            uniforms: HashMap::new(),
+            uniform_locations: HashMap::new(),
+            secondary_textures: BTreeMap::new(),
             vertex_shader: Some(vertex_shader),
             geometry_shader: None,
             fragment_shader: Some(fragment_shader),
@@ -141,8 +420,55 @@ pub fn init_framebuffer(
             vao,
             vbo,
             texture_format,
-        }
+            texture_swizzle: IDENTITY_SWIZZLE,
+            wrap_mode: WrapMode::Clamp,
+            texture_allocation: None,
+            has_content: false,
+            srgb: false,
+            target_framebuffer: 0,
+            target_renderbuffer: 0,
+            pbos: None,
+            pbo_index: 0,
+            gl_debug: false,
+            row_length: None,
+            mipmaps: false,
+            // the context may not be ours; whoever created it tags the framebuffer
+            context_id: 0,
+            framebuffer_id: NEXT_FRAMEBUFFER_ID.fetch_add(1, Ordering::Relaxed),
+        },
+        _not_send: PhantomData,
+    }
+}
+
+/// Redraws and presents several windows in one go: each breakout's context is made current (unless
+/// it already is), its framebuffer redrawn and its buffers swapped. This is the context switching
+/// dance every multi-window program needs, in one place and without `unsafe` on your end.
+///
+/// ```rust,no_run
+/// # use mini_gl_fb::{config, get_fancy};
+/// # use mini_gl_fb::glutin::event_loop::EventLoop;
+/// use mini_gl_fb::core::present_all;
+///
+/// # let event_loop = EventLoop::new();
+/// let mut left = get_fancy(config!(), &event_loop).glutin_breakout();
+/// let mut right = get_fancy(config!(), &event_loop).glutin_breakout();
+/// // ... update both buffers, then:
+/// present_all(&mut [&mut left, &mut right]).unwrap();
+/// ```
+///
+/// Swapping waits for the next vertical blank if vsync is on, so with vsync enabled on every
+/// window, presenting `n` windows can take `n` refreshes. Enable it on only one of them to keep
+/// the others from waiting.
+///
+/// Stops at the first context that can't be made current or swapped, and returns its error. The
+/// last presented window's context is left current.
+pub fn present_all(breakouts: &mut [&mut GlutinBreakout]) -> Result<(), ContextError> {
+    for breakout in breakouts.iter_mut() {
+        breakout.ensure_current()?;
+        breakout.fb.redraw();
+        breakout.context.swap_buffers()?;
     }
+    Ok(())
 }
 
 /// Hides away the guts of the library.
@@ -156,6 +482,46 @@ pub fn init_framebuffer(
 pub struct Internal {
     pub context: WindowedContext<PossiblyCurrent>,
     pub fb: Framebuffer,
+    /// Whether the context supports presenting only the damaged regions of the window. This is
+    /// queried once when the context is created.
+    pub damage_supported: bool,
+    /// The width / height ratio that the window is locked to, if any. See
+    /// [`MiniGlFb::lock_aspect_ratio`][crate::MiniGlFb::lock_aspect_ratio].
+    pub aspect_ratio: Option<f64>,
+    /// The state of [`glutin_handle_basic_input`][Internal::glutin_handle_basic_input] if the
+    /// handler suspended it, so that it can pick up where it left off.
+    pub suspended_input: Option<BasicInput>,
+    /// The key that closes the window in [`persist`][Internal::persist], if any. See
+    /// [`MiniGlFb::set_quit_key`][crate::MiniGlFb::set_quit_key].
+    pub quit_key: Option<VirtualKeyCode>,
+    /// Whether the cursor should be grabbed. Some platforms let go of it when the window loses
+    /// focus, so it is grabbed again whenever the window regains focus. See
+    /// [`MiniGlFb::set_cursor_grab`][crate::MiniGlFb::set_cursor_grab].
+    pub cursor_grab: bool,
+    /// Where gamepad input for [`BasicInput::gamepads`] comes from, or `None` if gamepads aren't
+    /// supported on this platform.
+    #[cfg(feature = "gilrs")]
+    pub gilrs: Option<gilrs::Gilrs>,
+    /// Decides whether the window actually closes when the user tries to close it. See
+    /// [`MiniGlFb::set_close_handler`][crate::MiniGlFb::set_close_handler].
+    pub close_handler: Option<Box<dyn FnMut() -> bool>>,
+    /// Whether the buffer is resized along with the window. See
+    /// [`MiniGlFb::set_buffer_follows_window`][crate::MiniGlFb::set_buffer_follows_window].
+    pub buffer_follows_window: bool,
+    /// Where the cursor was last seen in the window, in physical pixels, or `None` if it isn't
+    /// over the window. [`cursor_position`][Internal::cursor_position] converts it to buffer
+    /// coordinates.
+    pub window_cursor_position: Option<PhysicalPosition<f64>>,
+}
+
+/// What [`Internal::handle_persist_event`] did with an event.
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+enum PersistEvent {
+    /// The window should close.
+    Close,
+    /// The window was resized and has already been redrawn.
+    Redrawn,
+    Other,
 }
 
 impl Internal {
@@ -164,20 +530,304 @@ impl Internal {
         self.context.swap_buffers().unwrap();
     }
 
+    pub fn try_update_buffer<T>(&mut self, image_data: &[T]) -> Result<(), BufferSizeError> {
+        self.fb.try_update_buffer(image_data)?;
+        self.context.swap_buffers().unwrap();
+        Ok(())
+    }
+
+    pub fn update_buffer_flipped<T>(&mut self, image_data: &[T]) {
+        self.fb.update_buffer_flipped(image_data);
+        self.context.swap_buffers().unwrap();
+    }
+
+    pub fn update_buffer_region<T>(&mut self, data: &[T], x: u32, y: u32, width: u32, height: u32) {
+        self.fb.update_buffer_region(data, x, y, width, height);
+        self.context.swap_buffers().unwrap();
+    }
+
     pub fn set_resizable(&mut self, resizable: bool) {
         self.context.window().set_resizable(resizable);
     }
 
+    pub fn physical_size(&self) -> (u32, u32) {
+        self.context.window().inner_size().into()
+    }
+
+    pub fn logical_size(&self) -> (f64, f64) {
+        let window = self.context.window();
+        window.inner_size().to_logical::<f64>(window.scale_factor()).into()
+    }
+
+    pub fn scale_factor(&self) -> f64 {
+        self.context.window().scale_factor()
+    }
+
+    pub fn set_visible(&mut self, visible: bool) {
+        self.context.window().set_visible(visible);
+    }
+
+    pub fn set_minimized(&mut self, minimized: bool) {
+        self.context.window().set_minimized(minimized);
+    }
+
+    pub fn set_title(&mut self, title: &str) {
+        self.context.window().set_title(title);
+    }
+
+    pub fn set_cursor_icon(&mut self, icon: CursorIcon) {
+        self.context.window().set_cursor_icon(icon);
+    }
+
+    pub fn set_cursor_visible(&mut self, visible: bool) {
+        self.context.window().set_cursor_visible(visible);
+    }
+
+    pub fn set_cursor_grab(&mut self, grab: bool) -> Result<(), MglfbError> {
+        self.context.window().set_cursor_grab(grab)?;
+        self.cursor_grab = grab;
+        Ok(())
+    }
+
+    /// Grabs the cursor again after the window regains focus, if it's supposed to be grabbed.
+    fn regrab_cursor(&mut self, event: &WindowEvent) {
+        if let WindowEvent::Focused(true) = event {
+            if self.cursor_grab {
+                // if this fails now, it'll be tried again the next time the window is focused
+                let _ = self.context.window().set_cursor_grab(true);
+            }
+        }
+    }
+
+    /// Remembers where the cursor is, for [`cursor_position`][Internal::cursor_position] and for
+    /// the shaders' `u_mouse` (see [`Framebuffer::mouse_position`]).
+    fn track_cursor(&mut self, event: &WindowEvent) {
+        match event {
+            WindowEvent::CursorMoved { position, .. } => {
+                self.window_cursor_position = Some(*position);
+                // the viewport doesn't necessarily reach the top of the window
+                let window_height = self.context.window().inner_size().height;
+                self.fb.mouse_position = self.fb.window_to_content(*position, window_height);
+            }
+            WindowEvent::CursorLeft { .. } => self.window_cursor_position = None,
+            _ => {}
+        }
+    }
+
+    /// Where the cursor was last seen, in buffer coordinates. See
+    /// [`MiniGlFb::cursor_position`][crate::MiniGlFb::cursor_position].
+    pub fn cursor_position(&self) -> Option<(f64, f64)> {
+        let window_height = self.context.window().inner_size().height;
+        self.window_cursor_position.map(|position| self.fb.window_to_buffer(position, window_height))
+    }
+
+    pub fn set_cursor_position(&mut self, x: f64, y: f64) -> Result<(), MglfbError> {
+        let window_height = self.context.window().inner_size().height;
+        let position = self.fb.buffer_to_window((x, y), window_height);
+        self.context.window().set_cursor_position(position)?;
+        // some platforms don't report moves they didn't cause themselves
+        self.window_cursor_position = Some(position);
+        Ok(())
+    }
+
+    pub fn set_decorations(&mut self, decorations: bool) {
+        self.context.window().set_decorations(decorations);
+    }
+
+    pub fn set_always_on_top(&mut self, always_on_top: bool) {
+        self.context.window().set_always_on_top(always_on_top);
+    }
+
+    pub fn set_min_window_size(&mut self, size: Option<LogicalSize<f64>>) {
+        self.context.window().set_min_inner_size(size);
+    }
+
+    pub fn set_max_window_size(&mut self, size: Option<LogicalSize<f64>>) {
+        self.context.window().set_max_inner_size(size);
+    }
+
+    pub fn set_outer_position(&mut self, position: PhysicalPosition<i32>) {
+        self.context.window().set_outer_position(position);
+    }
+
+    pub fn current_monitor(&self) -> Option<MonitorHandle> {
+        self.context.window().current_monitor()
+    }
+
+    pub fn set_current_monitor(&mut self, monitor: &MonitorHandle) {
+        self.context.window().set_outer_position(monitor.position());
+    }
+
+    pub fn set_window_icon(&mut self, icon: Option<WindowIcon>) -> Result<(), MglfbError> {
+        let icon = icon.as_ref().map(WindowIcon::to_glutin).transpose()?;
+        self.context.window().set_window_icon(icon);
+        Ok(())
+    }
+
+    pub fn is_fullscreen(&self) -> bool {
+        self.context.window().fullscreen().is_some()
+    }
+
+    pub fn set_fullscreen(&mut self, fullscreen: bool) {
+        let window = self.context.window();
+        window.set_fullscreen(if fullscreen {
+            Some(Fullscreen::Borderless(window.current_monitor()))
+        } else {
+            None
+        });
+
+        let size = window.inner_size();
+        self.resize_viewport(size.width, size.height);
+    }
+
+    pub fn toggle_fullscreen(&mut self) {
+        self.set_fullscreen(!self.is_fullscreen());
+    }
+
+    pub fn current_refresh_rate(&self) -> Option<f32> {
+        // winit doesn't tell us which video mode is active, so take the fastest mode that matches
+        // the monitor's current resolution
+        let monitor = self.current_monitor()?;
+        let size = monitor.size();
+        monitor.video_modes()
+            .filter(|mode| mode.size() == size)
+            .map(|mode| mode.refresh_rate())
+            .max()
+            .map(|rate| rate as f32)
+    }
+
     pub fn resize_viewport(&mut self, width: u32, height: u32) {
+        // minimizing reports a size of 0x0 on some platforms; keep the old size until it's back
+        if width == 0 || height == 0 {
+            return;
+        }
         self.context.resize((width, height).into());
         self.fb.resize_viewport(width, height);
     }
 
+    pub fn wait_for_vblank(&self) -> bool {
+        if crate::vsync::wait_for_vblank(&self.context) {
+            return true;
+        }
+
+        let refresh_rate = self.current_refresh_rate().filter(|&rate| rate > 0.0).unwrap_or(60.0);
+        std::thread::sleep(Duration::from_secs_f64(1.0 / refresh_rate as f64));
+        false
+    }
+
+    pub fn set_vsync(&mut self, vsync: bool) -> Result<(), MglfbError> {
+        self.set_swap_interval(vsync as i32)
+    }
+
+    pub fn set_swap_interval(&mut self, interval: i32) -> Result<(), MglfbError> {
+        if crate::vsync::set_swap_interval(&self.context, interval) {
+            Ok(())
+        } else {
+            Err(MglfbError::VsyncUnsupported)
+        }
+    }
+
+    pub fn set_quit_key(&mut self, key: Option<VirtualKeyCode>) {
+        self.quit_key = key;
+    }
+
+    pub fn set_close_handler<F: FnMut() -> bool + 'static>(&mut self, handler: F) {
+        self.close_handler = Some(Box::new(handler));
+    }
+
+    /// Asks the close handler whether the window should close, when the user tries to close it.
+    fn should_close(&mut self) -> bool {
+        match &mut self.close_handler {
+            Some(handler) => handler(),
+            None => true,
+        }
+    }
+
+    pub fn set_buffer_follows_window(&mut self, follow: bool) {
+        self.buffer_follows_window = follow;
+        self.follow_window(self.context.window().inner_size());
+    }
+
+    /// If the buffer follows the window, resizes it to the logical size of a window that is `size`
+    /// physical pixels big.
+    fn follow_window(&mut self, size: PhysicalSize<u32>) {
+        if !self.buffer_follows_window || size.width == 0 || size.height == 0 {
+            return;
+        }
+        let size: LogicalSize<u32> = size.to_logical(self.context.window().scale_factor());
+        self.fb.resize_buffer(size.width.max(1), size.height.max(1));
+    }
+
+    pub fn lock_aspect_ratio(&mut self, aspect_ratio: Option<f64>) {
+        self.aspect_ratio = aspect_ratio;
+        self.constrain_to_aspect_ratio(self.context.window().inner_size());
+    }
+
+    /// If the aspect ratio is locked, finds the closest size to `size` that matches it and asks
+    /// the window to become that size. The viewport isn't touched: the window manager may ignore
+    /// the request, so the viewport keeps following the window's actual size until a `Resized`
+    /// event confirms the new one.
+    fn constrain_to_aspect_ratio(&self, size: PhysicalSize<u32>) {
+        let ratio = match self.aspect_ratio {
+            Some(ratio) if size.width > 0 && size.height > 0 => ratio,
+            _ => return,
+        };
+
+        // keep the area the same, so that dragging any edge or corner grows or shrinks the window
+        // in a way that feels natural
+        let area = size.width as f64 * size.height as f64;
+        let width = (area * ratio).sqrt().round();
+        let height = (width / ratio).round();
+        let constrained = PhysicalSize::new(width.max(1.0) as u32, height.max(1.0) as u32);
+
+        if constrained != size {
+            self.context.window().set_inner_size(constrained);
+        }
+    }
+
+    pub fn show<P: IntoPixels>(&mut self, pixels: &[P]) {
+        self.fb.show(pixels);
+        self.context.swap_buffers().unwrap();
+    }
+
+    pub fn try_show<P: IntoPixels>(&mut self, pixels: &[P]) -> Result<(), BufferSizeError> {
+        self.fb.try_show(pixels)?;
+        self.context.swap_buffers().unwrap();
+        Ok(())
+    }
+
+    pub fn flush_draw_buffer<P: IntoPixels + Copy>(&mut self, buffer: &mut DrawBuffer<P>) -> bool {
+        let flushed = buffer.flush(&mut self.fb);
+        if flushed {
+            self.context.swap_buffers().unwrap();
+        }
+        flushed
+    }
+
+    pub fn upload<T: ToGlType>(&mut self, upload: BufferUpload<T>) -> Vec<T> {
+        let data = self.fb.upload(upload);
+        self.context.swap_buffers().unwrap();
+        data
+    }
+
     pub fn redraw(&mut self) {
         self.fb.redraw();
         self.context.swap_buffers().unwrap();
     }
 
+    pub fn clear(&mut self, color: [f32; 4]) {
+        self.fb.clear(color);
+        self.context.swap_buffers().unwrap();
+    }
+
+    pub fn swap_buffers_with_damage(&mut self, rects: &[Rect]) {
+        if self.damage_supported {
+            self.context.swap_buffers_with_damage(rects).unwrap();
+        } else {
+            self.context.swap_buffers().unwrap();
+        }
+    }
+
     pub fn persist<ET: 'static>(&mut self, event_loop: &mut EventLoop<ET>) {
         self.persist_and_redraw(event_loop, false);
     }
@@ -186,43 +836,163 @@ impl Internal {
         event_loop.run_return(|event, _, flow| {
             *flow = ControlFlow::Wait;
 
-            let mut new_size = None;
-            match event {
-                Event::WindowEvent { event, .. } => match event {
-                    WindowEvent::CloseRequested => *flow = ControlFlow::Exit,
-                    WindowEvent::KeyboardInput { input, .. } => {
-                        if let Some(k) = input.virtual_keycode {
-                            if k == VirtualKeyCode::Escape
-                                    && input.state == ElementState::Pressed {
-                                *flow = ControlFlow::Exit;
-                            }
-                        }
+            match self.handle_persist_event(&event) {
+                PersistEvent::Close => *flow = ControlFlow::Exit,
+                // a resize already redrew, don't draw and swap a second time
+                PersistEvent::Redrawn => {}
+                PersistEvent::Other => {
+                    if redraw {
+                        self.redraw();
                     }
-                    WindowEvent::Resized(physical_size) => {
-                        new_size = Some(physical_size);
+                }
+            }
+        });
+    }
+
+    pub fn persist_with_framerate<ET: 'static>(&mut self, event_loop: &mut EventLoop<ET>, fps: f64) {
+        assert!(fps > 0.0, "The framerate must be positive, got {}", fps);
+        self.persist_animated(event_loop, Duration::from_secs_f64(1.0 / fps), |_| {});
+    }
+
+    pub fn persist_animated<ET: 'static, F: FnMut(&mut Framebuffer)>(
+        &mut self, event_loop: &mut EventLoop<ET>, frame_time: Duration, mut frame: F
+    ) {
+        let mut next_frame = Instant::now() + frame_time;
+
+        event_loop.run_return(|event, _, flow| {
+            if self.handle_persist_event(&event) == PersistEvent::Close {
+                *flow = ControlFlow::Exit;
+                return;
+            }
+
+            if let Event::MainEventsCleared = event {
+                let now = Instant::now();
+                if now >= next_frame {
+                    frame(&mut self.fb);
+                    self.redraw();
+                    next_frame += frame_time;
+                    // don't try to catch up on frames we missed, e.g. while the window was dragged
+                    if next_frame <= now {
+                        next_frame = now + frame_time;
                     }
-                    _ => {},
-                },
-                _ => {},
+                }
             }
 
-            if let Some(size) = new_size {
-                self.resize_viewport(size.width, size.height);
-                self.redraw();
-            } else if redraw {
-                self.fb.redraw();
-                self.context.swap_buffers().unwrap();
+            *flow = ControlFlow::WaitUntil(next_frame);
+        });
+    }
+
+    pub fn persist_until<ET: 'static, F: FnMut() -> bool>(
+        &mut self, event_loop: &mut EventLoop<ET>, mut done: F
+    ) {
+        event_loop.run_return(|event, _, flow| {
+            *flow = ControlFlow::Poll;
+
+            if self.handle_persist_event(&event) == PersistEvent::Close {
+                *flow = ControlFlow::Exit;
+            } else if let Event::MainEventsCleared = event {
+                if done() {
+                    *flow = ControlFlow::Exit;
+                } else {
+                    self.redraw();
+                }
+            }
+        });
+    }
+
+    pub fn persist_frames<ET: 'static>(&mut self, event_loop: &mut EventLoop<ET>, frames: u32) {
+        let mut presented = 0;
+        self.persist_until(event_loop, || {
+            if presented == frames {
+                return true;
             }
+            presented += 1;
+            false
         });
     }
 
+    /// Handles closing and resizing for the `persist` loops, and reports what it did.
+    fn handle_persist_event<ET>(&mut self, event: &Event<ET>) -> PersistEvent {
+        if let Event::WindowEvent { event, .. } = event {
+            self.regrab_cursor(event);
+            self.track_cursor(event);
+        }
+
+        match event {
+            Event::WindowEvent { event, .. } => match event {
+                WindowEvent::CloseRequested if self.should_close() => return PersistEvent::Close,
+                WindowEvent::KeyboardInput { input, .. }
+                        if input.virtual_keycode.is_some()
+                            && input.virtual_keycode == self.quit_key
+                            && input.state == ElementState::Pressed => {
+                    return PersistEvent::Close;
+                }
+                WindowEvent::Resized(physical_size) => {
+                    self.constrain_to_aspect_ratio(*physical_size);
+                    self.resize_viewport(physical_size.width, physical_size.height);
+                    self.follow_window(*physical_size);
+                    self.redraw();
+                    return PersistEvent::Redrawn;
+                }
+                _ => {},
+            },
+            _ => {},
+        }
+
+        PersistEvent::Other
+    }
+
     pub fn glutin_handle_basic_input<ET: 'static, F: FnMut(&mut Framebuffer, &mut BasicInput) -> bool>(
-        &mut self, event_loop: &mut EventLoop<ET>, mut handler: F
+        &mut self, event_loop: &mut EventLoop<ET>, handler: F
     ) {
+        self.basic_input_loop(event_loop, handler, None::<(Duration, fn(&mut Framebuffer))>);
+    }
+
+    pub fn glutin_handle_basic_input_with_redraw<ET, F, R>(
+        &mut self, event_loop: &mut EventLoop<ET>, fps: f64, handler: F, redraw: R
+    ) where
+        ET: 'static,
+        F: FnMut(&mut Framebuffer, &mut BasicInput) -> bool,
+        R: FnMut(&mut Framebuffer),
+    {
+        assert!(fps > 0.0, "The framerate must be positive, got {}", fps);
+        if self.suspended_input.is_none() {
+            // input is only passed on when it changes, the frames are drawn separately
+            let mut input = BasicInput::default();
+            input.wait = true;
+            self.suspended_input = Some(input);
+        }
+        self.basic_input_loop(event_loop, handler, Some((Duration::from_secs_f64(1.0 / fps), redraw)));
+    }
+
+    /// The loop behind `glutin_handle_basic_input`. If `frames` is given, its closure is also
+    /// called every time the given frame time has passed.
+    fn basic_input_loop<ET, F, R>(
+        &mut self,
+        event_loop: &mut EventLoop<ET>,
+        mut handler: F,
+        mut frames: Option<(Duration, R)>,
+    ) where
+        ET: 'static,
+        F: FnMut(&mut Framebuffer, &mut BasicInput) -> bool,
+        R: FnMut(&mut Framebuffer),
+    {
         let mut previous_input: Option<BasicInput> = None;
-        let mut input = BasicInput::default();
+        let mut input = self.suspended_input.take().unwrap_or_default();
+        input.scale_factor = self.context.window().scale_factor();
+        let mut next_frame = Instant::now();
+        #[cfg(feature = "gilrs")]
+        if let Some(gilrs) = &self.gilrs {
+            crate::gamepad::add_connected(gilrs, &mut input.gamepads);
+        }
 
         event_loop.run_return(|event, _, flow| {
+            if input.suspend {
+                // don't call the handler again while the loop is winding down
+                *flow = ControlFlow::Exit;
+                return;
+            }
+
             let mut new_size = None;
             let mut new_mouse_pos: Option<PhysicalPosition<f64>> = None;
 
@@ -235,9 +1005,19 @@ impl Internal {
                 val.0 = val.1;
             }
 
+            #[cfg(feature = "gilrs")]
+            crate::gamepad::store_previous(&mut input.gamepads);
+
+            input.clear_double_clicks();
+
+            if let Event::WindowEvent { event, .. } = &event {
+                self.regrab_cursor(event);
+                self.track_cursor(event);
+            }
+
             match &event {
                 Event::WindowEvent { event, .. } => match event {
-                    WindowEvent::CloseRequested => {
+                    WindowEvent::CloseRequested if self.should_close() => {
                         *flow = ControlFlow::Exit;
                         return;
                     },
@@ -257,40 +1037,65 @@ impl Internal {
                         new_mouse_pos = Some(*position);
                     }
                     WindowEvent::MouseInput { state, button, .. } => {
+                        let pressed = *state == ElementState::Pressed;
+                        if pressed && !input.mouse_is_down(*button) {
+                            input.register_click(*button);
+                        }
                         let button = input.mouse.entry(*button)
                             .or_insert((false, false));
-                        button.1 = *state == ElementState::Pressed;
+                        button.1 = pressed;
                     }
                     WindowEvent::ModifiersChanged(modifiers) => {
                         input.modifiers = *modifiers;
                     }
-                    WindowEvent::Resized(logical_size) => {
-                        new_size = Some(*logical_size);
+                    WindowEvent::ReceivedCharacter(character) => {
+                        input.text.push(*character);
+                    }
+                    WindowEvent::HoveredFile(_) => {
+                        input.hovering_files = true;
+                    }
+                    WindowEvent::HoveredFileCancelled => {
+                        input.hovering_files = false;
+                    }
+                    WindowEvent::DroppedFile(path) => {
+                        input.hovering_files = false;
+                        input.dropped_files.push(path.clone());
+                    }
+                    WindowEvent::Resized(logical_size) => {
+                        input.minimized = logical_size.width == 0 || logical_size.height == 0;
+                        new_size = Some(*logical_size);
+                    }
+                    WindowEvent::Focused(focused) => {
+                        input.focused = *focused;
+                    }
+                    WindowEvent::ScaleFactorChanged { scale_factor, new_inner_size } => {
+                        // not every platform follows this up with a `Resized`
+                        input.scale_factor = *scale_factor;
+                        new_size = Some(**new_inner_size);
                     }
                     _ => {}
                 },
                 _ => {}
             }
 
+            // gamepads are checked once per iteration of the event loop
+            #[cfg(feature = "gilrs")]
+            if let (Event::MainEventsCleared, Some(gilrs)) = (&event, &mut self.gilrs) {
+                crate::gamepad::update(gilrs, &mut input.gamepads);
+            }
+
             if let Some(size) = new_size {
+                self.constrain_to_aspect_ratio(size);
                 self.resize_viewport(size.width, size.height);
+                self.follow_window(size);
                 input.resized = true;
             }
 
             if let Some(pos) = new_mouse_pos {
-                let (x, y): (f64, f64) = pos.into();
-                let x_scale = self.fb.buffer_size.width as f64 / (self.fb.vp_size.width as f64);
-                let y_scale = self.fb.buffer_size.height as f64 / (self.fb.vp_size.height as f64);
-                let mouse_pos = (
-                    x * x_scale,
-                    // use the OpenGL texture coordinate system instead of window coordinates
-                    if self.fb.inverted_y {
-                        self.fb.buffer_size.height as f64 - y * y_scale
-                    } else {
-                        y * y_scale
-                    }
-                );
-                input.mouse_pos = mouse_pos;
+                input.mouse_pos_window = pos.to_logical::<f64>(input.scale_factor).into();
+
+                let window_height = self.context.window().inner_size().height;
+                input.mouse_pos = self.fb.window_to_buffer(pos, window_height);
             }
 
             while let Some(wakeup) = input.wakeups.get(0) {
@@ -299,15 +1104,25 @@ impl Internal {
                 input.wakeup = Some(*wakeup);
                 input.wakeups.remove(0);
 
+                input.start_frame();
                 if !handler(&mut self.fb, &mut input) {
                     *flow = ControlFlow::Exit;
                     return;
                 }
+                // the handler has seen the text and files now
+                input.text.clear();
+                input.dropped_files.clear();
+
+                if input.suspend {
+                    break;
+                }
             }
 
             input.wakeup = None;
 
-            if input.wait {
+            if input.suspend {
+                *flow = ControlFlow::Exit;
+            } else if input.wait || (input.wait_when_minimized && input.minimized) {
                 if let Some(wakeup) = input.wakeups.get(0) {
                     *flow = ControlFlow::WaitUntil(wakeup.when)
                 } else {
@@ -319,6 +1134,7 @@ impl Internal {
                     // wakeups have already been handled
                     if let Event::NewEvents(StartCause::ResumeTimeReached { .. }) = &event {
                     } else {
+                        input.start_frame();
                         if !handler(&mut self.fb, &mut input) {
                             *flow = ControlFlow::Exit;
                         }
@@ -326,6 +1142,7 @@ impl Internal {
                 }
             } else {
                 // handler wants to be notified regardless
+                input.start_frame();
                 if !handler(&mut self.fb, &mut input) {
                     *flow = ControlFlow::Exit;
                 } else {
@@ -333,13 +1150,68 @@ impl Internal {
                 }
             }
 
+            input.text.clear();
+            input.dropped_files.clear();
             previous_input = Some(input.clone());
 
+            // gamepads can't wake up the event loop, so check on them regularly
+            #[cfg(feature = "gilrs")]
+            if input.gamepads.iter().any(|pad| pad.connected) {
+                let poll = Instant::now() + crate::gamepad::POLL_INTERVAL;
+                *flow = match *flow {
+                    ControlFlow::Wait => ControlFlow::WaitUntil(poll),
+                    ControlFlow::WaitUntil(when) => ControlFlow::WaitUntil(when.min(poll)),
+                    flow => flow,
+                };
+            }
+
+            if let Some((frame_time, redraw)) = &mut frames {
+                if let Event::MainEventsCleared = event {
+                    let now = Instant::now();
+                    if now >= next_frame && !input.suspend && *flow != ControlFlow::Exit {
+                        redraw(&mut self.fb);
+                        next_frame += *frame_time;
+                        // don't try to catch up on frames we missed
+                        if next_frame <= now {
+                            next_frame = now + *frame_time;
+                        }
+                    }
+                }
+                // wake up in time for the next frame
+                *flow = match *flow {
+                    ControlFlow::Wait => ControlFlow::WaitUntil(next_frame),
+                    ControlFlow::WaitUntil(when) => ControlFlow::WaitUntil(when.min(next_frame)),
+                    flow => flow,
+                };
+            }
+
             if self.fb.did_draw {
                 self.context.swap_buffers().unwrap();
                 self.fb.did_draw = false;
             }
+
+            if input.suspend {
+                *flow = ControlFlow::Exit;
+            }
         });
+
+        if input.suspend {
+            // Keys and buttons may be released while we aren't listening, so forget about them
+            // rather than have them get stuck down when the handler is resumed
+            input.suspend = false;
+            input.keys.clear();
+            input.mouse.clear();
+            #[cfg(feature = "gilrs")]
+            for pad in &mut input.gamepads {
+                pad.buttons.clear();
+            }
+            input.reset_frame_time();
+            self.suspended_input = Some(input);
+        }
+    }
+
+    pub fn breakout_ref(&mut self) -> (&WindowedContext<PossiblyCurrent>, &mut Framebuffer) {
+        (&self.context, &mut self.fb)
     }
 
     pub fn glutin_breakout(self) -> GlutinBreakout {
@@ -354,8 +1226,28 @@ impl Internal {
 #[non_exhaustive]
 #[derive(Debug)]
 pub struct FramebufferInternal {
+    /// The program that is drawn with: either `linked_program` or one of `programs`.
     pub program: GLuint,
+    /// The program linked from `vertex_shader`, `fragment_shader` and `geometry_shader`.
+    pub linked_program: GLuint,
+    /// Programs registered with [`Framebuffer::register_program`], with their names. A
+    /// [`ProgramHandle`] holds an index into this.
+    pub programs: Vec<(String, GLuint)>,
     pub sampler_location: GLint,
+    /// Locations of the `u_resolution`, `u_time` and `u_mouse` uniforms, or -1 if the program
+    /// doesn't use them.
+    pub resolution_location: GLint,
+    pub time_location: GLint,
+    pub mouse_location: GLint,
+    /// The point in time `u_time` counts from.
+    pub start_time: Instant,
+    /// Values set with the `set_uniform_*` methods, applied on every draw.
+    pub uniforms: HashMap<String, UniformValue>,
+    /// Cached locations of the uniforms in `uniforms`, by program and name, so that switching
+    /// between programs keeps them. A program's entries are dropped when it is deleted.
+    pub uniform_locations: HashMap<(GLuint, String), GLint>,
+    /// Extra textures set with `set_secondary_texture`, by texture unit.
+    pub secondary_textures: BTreeMap<u32, GLuint>,
     pub vertex_shader: Option<GLuint>,
     pub geometry_shader: Option<GLuint>,
     pub fragment_shader: Option<GLuint>,
@@ -363,6 +1255,43 @@ pub struct FramebufferInternal {
     pub vao: GLuint,
     pub vbo: GLuint,
     pub texture_format: (BufferFormat, GLenum),
+    pub texture_swizzle: [GLenum; 4],
+    /// See [`Framebuffer::set_wrap_mode`].
+    pub wrap_mode: WrapMode,
+    /// The size and format the texture storage was last allocated with, or `None` if nothing has
+    /// been uploaded yet. Buffer updates that match it reuse the storage.
+    pub texture_allocation: Option<(LogicalSize<i32>, (BufferFormat, GLenum))>,
+    /// Whether there is anything to draw yet: a buffer has been uploaded, or a shader of the
+    /// user's own is in use, which may not need one. Until then, [`Framebuffer::redraw`] only
+    /// clears to the background color. Unlike `texture_allocation`, this is never reset.
+    pub has_content: bool,
+    /// Whether the buffer is treated as sRGB. See [`Framebuffer::set_srgb`].
+    pub srgb: bool,
+    /// The OpenGL framebuffer object that is drawn to and read from. This is 0, the window, except
+    /// for [`Headless`] framebuffers.
+    pub target_framebuffer: GLuint,
+    /// The renderbuffer holding the image of `target_framebuffer`, or 0 if that is the window.
+    /// Both are deleted when the [`Headless`] is dropped.
+    pub target_renderbuffer: GLuint,
+    /// The two pixel buffer objects used for uploads if
+    /// [`enable_pbo_streaming`][Framebuffer::enable_pbo_streaming] is on.
+    pub pbos: Option<[GLuint; 2]>,
+    /// Which of `pbos` the next upload goes through.
+    pub pbo_index: usize,
+    /// Whether OpenGL errors are logged. See [`Framebuffer::enable_gl_debug`].
+    pub gl_debug: bool,
+    /// The distance between rows in uploaded pixel data. See [`Framebuffer::set_row_length`].
+    pub row_length: Option<u32>,
+    /// Whether the buffer texture has mipmaps. See [`Framebuffer::enable_mipmaps`].
+    pub mipmaps: bool,
+    /// Identifies the context that was current when the framebuffer was created, which its OpenGL
+    /// objects belong to. 0 if the context wasn't created by `mini_gl_fb` (including framebuffers
+    /// made with [`init_framebuffer`] directly), which turns off the warning in debug builds about
+    /// drawing while another context is current.
+    pub context_id: u64,
+    /// Unique to this framebuffer. Stored in every [`ProgramHandle`] it hands out, so a handle
+    /// from another framebuffer is caught instead of picking whatever program has the same index.
+    pub framebuffer_id: u64,
 }
 
 /// The Framebuffer struct manages the framebuffer of a MGlFb window. Through this struct, you can
@@ -397,6 +1326,20 @@ pub struct Framebuffer {
     /// buffers to one window or something funky like that.
     pub vp_size: PhysicalSize<i32>,
 
+    /// The physical position of the bottom left corner of the viewport within the window. This is
+    /// `(0, 0)` unless you are drawing multiple buffers to one window, for instance with a
+    /// [`TiledView`][crate::tiled::TiledView].
+    pub vp_position: PhysicalPosition<i32>,
+
+    /// The last known position of the mouse in physical pixels, relative to the bottom left corner
+    /// of the viewport. Shaders can read it as `u_mouse`.
+    ///
+    /// [`persist`][crate::MiniGlFb::persist],
+    /// [`glutin_handle_basic_input`][crate::MiniGlFb::glutin_handle_basic_input] and
+    /// [`GlutinBreakout::handle_event`] keep this up to date. If you handle events entirely
+    /// yourself, set it from `CursorMoved` events if your shaders need it.
+    pub mouse_position: PhysicalPosition<f64>,
+
     /// This is set to `true` every time [`draw`][Framebuffer::draw] is called. (or, by extension,
     /// [`update_buffer`][Framebuffer::update_buffer])
     ///
@@ -409,6 +1352,29 @@ pub struct Framebuffer {
     /// [`Config`][crate::Config] passed to [`get_fancy`][crate::get_fancy].
     pub inverted_y: bool,
 
+    /// How the buffer is fitted into the viewport. See [`ScaleMode`].
+    pub scale_mode: ScaleMode,
+
+    /// The RGBA color (components from 0 to 1) that fills the unused parts of the viewport in
+    /// [`ScaleMode::AspectFit`].
+    pub letterbox_color: [f32; 4],
+
+    /// The RGBA color (components from 0 to 1) that [`redraw`][Framebuffer::redraw] fills the
+    /// viewport with as long as no buffer has been uploaded, since the texture has no contents yet.
+    /// Once a shader of your own is in use, the buffer is drawn anyway, since such a shader may
+    /// not need one.
+    pub background_color: [f32; 4],
+
+    /// How the buffer is composited onto the window contents. See [`BlendMode`].
+    pub blend_mode: BlendMode,
+
+    /// Whether the buffer's colors are premultiplied by alpha. See [`AlphaMode`].
+    pub alpha_mode: AlphaMode,
+
+    /// The RGBA color (components from 0 to 1) the viewport is cleared to before every draw, if
+    /// any. See [`set_clear_color`][Framebuffer::set_clear_color].
+    pub clear_color: Option<[f32; 4]>,
+
     /// Contains internal OpenGL things.
     ///
     /// Accessing fields directly is not the intended usage. If a feature is missing please open an
@@ -416,64 +1382,573 @@ pub struct Framebuffer {
     /// exposed, if you need something in a pinch you can dig in easily and make it happen.
     ///
     /// The internal fields may change.
-    pub internal: FramebufferInternal
+    pub internal: FramebufferInternal,
+
+    // OpenGL calls are only valid on the thread the context is current on, so the `Framebuffer`
+    // must never leave it. Use a `BufferUpload` to hand data over from other threads instead.
+    _not_send: PhantomData<*const ()>,
 }
 
 impl Framebuffer {
     pub fn update_buffer<T>(&mut self, image_data: &[T]) {
+        if let Err(err) = self.try_update_buffer(image_data) {
+            panic!("{}", err);
+        }
+    }
+
+    pub fn try_update_buffer<T>(&mut self, image_data: &[T]) -> Result<(), BufferSizeError> {
+        self.upload_buffer(image_data, self.internal.row_length)
+    }
+
+    /// Checks that `actual` bytes are the right amount for the whole buffer, with pixels of
+    /// `pixel_size` bytes whose rows are `row_length` pixels apart. Returns how many bytes an
+    /// upload reads.
+    fn check_buffer_size(
+        &self,
+        pixel_size: usize,
+        actual: usize,
+        row_length: Option<u32>,
+    ) -> Result<usize, BufferSizeError> {
+        let width = self.buffer_size.width as u32;
+        let height = self.buffer_size.height as u32;
+        if matches!(row_length, Some(row_length) if row_length < width) {
+            // the rows would overlap, so no amount of data fits; ask for a tightly packed buffer
+            return Err(BufferSizeError {
+                expected: pixel_size * width as usize * height as usize,
+                actual,
+            });
+        }
+        let expected = pixel_size * strided_pixel_count(row_length, width, height);
+        // with a row length, whatever comes after the last row is none of our business
+        let wrong_size = match row_length {
+            Some(_) => actual < expected,
+            None => actual != expected,
+        };
+        if wrong_size {
+            return Err(BufferSizeError { expected, actual });
+        }
+        Ok(expected)
+    }
+
+    fn upload_buffer<T>(
+        &mut self,
+        image_data: &[T],
+        row_length: Option<u32>,
+    ) -> Result<(), BufferSizeError> {
         // Check the length of the passed slice so this is actually a safe method.
         let (format, kind) = self.internal.texture_format;
+        let pixel_size = size_of_gl_type_enum(kind) * format.components();
+        let expected = self.check_buffer_size(pixel_size, size_of_val(image_data), row_length)?;
+        let internal_format = self.texture_internal_format(kind);
+        let kind = transfer_type(format, kind);
+        // Reallocating the texture every frame is slow, so only do it when the buffer has changed
+        let allocation = Some((self.buffer_size, self.internal.texture_format));
+        let reallocate = self.internal.texture_allocation != allocation;
+        // new storage is allocated synchronously, streaming only applies to updates
+        let pbo = match self.internal.pbos {
+            Some(pbos) if !reallocate => Some(pbos[self.internal.pbo_index]),
+            _ => None,
+        };
+        self.draw(|fb| {
+            unsafe {
+                gl::PixelStorei(gl::UNPACK_ROW_LENGTH, row_length.unwrap_or(0) as _);
+                if let Some(pbo) = pbo {
+                    gl::BindBuffer(gl::PIXEL_UNPACK_BUFFER, pbo);
+                    // orphan the old storage rather than wait for the GPU to be done with it
+                    gl::BufferData(
+                        gl::PIXEL_UNPACK_BUFFER,
+                        expected as _,
+                        std::ptr::null(),
+                        gl::STREAM_DRAW,
+                    );
+                    let mapped = gl::MapBufferRange(
+                        gl::PIXEL_UNPACK_BUFFER,
+                        0,
+                        expected as _,
+                        gl::MAP_WRITE_BIT | gl::MAP_INVALIDATE_BUFFER_BIT,
+                    );
+                    if mapped.is_null() {
+                        gl::BufferSubData(
+                            gl::PIXEL_UNPACK_BUFFER,
+                            0,
+                            expected as _,
+                            image_data.as_ptr() as *const _,
+                        );
+                    } else {
+                        std::ptr::copy_nonoverlapping(
+                            image_data.as_ptr() as *const u8,
+                            mapped as *mut u8,
+                            expected,
+                        );
+                        gl::UnmapBuffer(gl::PIXEL_UNPACK_BUFFER);
+                    }
+                    // with a buffer bound, the data pointer is an offset into it
+                    gl::TexSubImage2D(
+                        gl::TEXTURE_2D,
+                        0,
+                        0,
+                        0,
+                        fb.buffer_size.width,
+                        fb.buffer_size.height,
+                        format as GLenum,
+                        kind,
+                        std::ptr::null(),
+                    );
+                    gl::BindBuffer(gl::PIXEL_UNPACK_BUFFER, 0);
+                } else if reallocate {
+                    gl::TexImage2D(
+                        gl::TEXTURE_2D,
+                        0,
+                        internal_format as _,
+                        fb.buffer_size.width,
+                        fb.buffer_size.height,
+                        0,
+                        format as GLenum,
+                        kind,
+                        image_data.as_ptr() as *const _,
+                    );
+                } else {
+                    gl::TexSubImage2D(
+                        gl::TEXTURE_2D,
+                        0,
+                        0,
+                        0,
+                        fb.buffer_size.width,
+                        fb.buffer_size.height,
+                        format as GLenum,
+                        kind,
+                        image_data.as_ptr() as *const _,
+                    );
+                }
+                gl::PixelStorei(gl::UNPACK_ROW_LENGTH, 0);
+                if fb.internal.mipmaps {
+                    gl::GenerateMipmap(gl::TEXTURE_2D);
+                }
+            }
+        });
+        self.internal.texture_allocation = allocation;
+        self.internal.has_content = true;
+        if pbo.is_some() {
+            self.internal.pbo_index = 1 - self.internal.pbo_index;
+        }
+        Ok(())
+    }
+
+    /// Starts logging OpenGL errors and warnings through the [`log`](https://docs.rs/log) crate, so
+    /// you need a logger like `env_logger` to see them.
+    ///
+    /// If the driver supports `KHR_debug` (OpenGL 4.3 and many older drivers), it reports problems
+    /// as they happen, with a description, and this returns `true`. Either way, `glGetError` is
+    /// checked after every draw and anything it reports is logged, which debug builds do even
+    /// without this.
+    ///
+    /// Drivers tend to say more in debug contexts, which `mini_gl_fb` doesn't request, so how
+    /// chatty this is depends a lot on the driver.
+    pub fn enable_gl_debug(&mut self) -> bool {
+        self.internal.gl_debug = true;
+        if !gl::DebugMessageCallback::is_loaded() {
+            return false;
+        }
+        unsafe {
+            gl::Enable(gl::DEBUG_OUTPUT);
+            // report from within the call that caused it, so the message lines up with the logs
+            gl::Enable(gl::DEBUG_OUTPUT_SYNCHRONOUS);
+            gl::DebugMessageCallback(log_gl_debug_message, std::ptr::null());
+            // contexts without KHR_debug may still have loaded the function, but fail here
+            gl::GetError() == gl::NO_ERROR
+        }
+    }
+
+    /// Turns streaming uploads through pixel buffer objects (PBOs) on or off.
+    ///
+    /// Normally [`update_buffer`][Framebuffer::update_buffer] hands the pixels straight to
+    /// OpenGL, which has to finish copying them before it returns. With streaming, the pixels are
+    /// copied into one of two PBOs instead and the GPU picks them up asynchronously while the next
+    /// frame is written into the other one. This can help a lot with large buffers that change
+    /// every frame, like video; for small or rarely updated buffers it's just an extra copy, which
+    /// is why it's off by default.
+    ///
+    /// Only whole buffer updates are streamed. The first upload after the size or format changes,
+    /// and [`update_buffer_region`][Framebuffer::update_buffer_region], still go the regular way.
+    pub fn enable_pbo_streaming(&mut self, enable: bool) {
+        match (enable, self.internal.pbos) {
+            (true, None) => {
+                let mut pbos = [0; 2];
+                unsafe { gl::GenBuffers(2, pbos.as_mut_ptr()); }
+                self.internal.pbos = Some(pbos);
+                self.internal.pbo_index = 0;
+            }
+            (false, Some(pbos)) => {
+                unsafe { gl::DeleteBuffers(2, pbos.as_ptr()); }
+                self.internal.pbos = None;
+            }
+            _ => {}
+        }
+    }
+
+    /// Like [`update_buffer`][Framebuffer::update_buffer], but the first row of `image_data` is
+    /// always the top one, the way the `image` crate, screenshots and most other sources lay out
+    /// pixels, whatever [`inverted_y`][Framebuffer::inverted_y] is. When the buffer's origin is the
+    /// bottom left, the rows are reversed on the way to the GPU, which costs a copy of the buffer.
+    ///
+    /// The [`row_length`][Framebuffer::set_row_length] is honored, and measured from the top row.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the size of the buffer is wrong, just like `update_buffer`.
+    pub fn update_buffer_flipped<T>(&mut self, image_data: &[T]) {
+        let (format, kind) = self.internal.texture_format;
+        let pixel_size = size_of_gl_type_enum(kind) * format.components();
+        let width = self.buffer_size.width as u32;
+        let height = self.buffer_size.height as u32;
+        let row_length = self.internal.row_length;
+        let bytes = unsafe {
+            std::slice::from_raw_parts(image_data.as_ptr() as *const u8, size_of_val(image_data))
+        };
+        let expected = pixel_size * strided_pixel_count(row_length, width, height);
+        let right_size = match row_length {
+            Some(_) => bytes.len() >= expected,
+            None => bytes.len() == expected,
+        };
+        // a buffer of the wrong size can't be split into rows; let the normal path report it
+        if !self.inverted_y || !right_size {
+            self.update_buffer(image_data);
+        } else {
+            let row_size = pixel_size * width as usize;
+            let stride = pixel_size * row_length.unwrap_or(width) as usize;
+            // the copy is tightly packed, whatever the row length
+            let flipped: Vec<u8> = (0..height as usize)
+                .rev()
+                .flat_map(|row| &bytes[row * stride..row * stride + row_size])
+                .copied()
+                .collect();
+            if let Err(err) = self.upload_buffer(&flipped, None) {
+                panic!("{}", err);
+            }
+        }
+    }
+
+    /// Sets the distance from the start of one row to the start of the next in the pixel data
+    /// passed to [`update_buffer`][Framebuffer::update_buffer],
+    /// [`update_buffer_region`][Framebuffer::update_buffer_region] and
+    /// [`update_sprite`][Framebuffer::update_sprite], in pixels (`GL_UNPACK_ROW_LENGTH`). `None`,
+    /// the default, means rows are tightly packed.
+    ///
+    /// This allows uploading part of a larger image without copying it into a buffer of its own
+    /// first. Pass a slice that starts at the first pixel to upload; it only needs to reach as far
+    /// as the last pixel that is read, so it can run on to the end of the larger image.
+    ///
+    /// ```rust,no_run
+    /// # let (_, mut fb) = mini_gl_fb::gotta_go_fast("Hello world!", 100.0, 100.0);
+    /// // a 100x100 view into the middle of a 400x300 image
+    /// let image = vec![[0u8, 0, 0, 255]; 400 * 300];
+    /// let (x, y) = (150, 100);
+    ///
+    /// fb.set_row_length(Some(400));
+    /// fb.update_buffer(&image[y * 400 + x..]);
+    /// ```
+    ///
+    /// A row length shorter than the rows being uploaded makes them overlap, so
+    /// [`try_update_buffer`][Framebuffer::try_update_buffer] returns a [`BufferSizeError`] for
+    /// it (with the size a tightly packed buffer would have), and the other uploads panic. This is
+    /// checked on upload, since resizing the buffer can make a row length too short.
+    pub fn set_row_length(&mut self, row_length: Option<u32>) {
+        self.internal.row_length = row_length;
+    }
+
+    /// Replaces a rectangle of the buffer and redraws, without re-uploading the rest of it. This is
+    /// much cheaper than [`update_buffer`][Framebuffer::update_buffer] when only a small part of
+    /// the buffer changes each frame, like in a paint program or a terminal emulator.
+    ///
+    /// `data` contains `width * height` pixels in the current buffer format, tightly packed unless a
+    /// [`row_length`][Framebuffer::set_row_length] is set, and is copied into the buffer with its
+    /// first pixel at `(x, y)`. Coordinates and row order are
+    /// the same as in the buffer you pass to `update_buffer`: if
+    /// [`inverted_y`][Framebuffer::inverted_y] is `true`, `(0, 0)` is the bottom left and rows go
+    /// up, otherwise it is the top left and rows go down. No flipping is needed either way.
+    ///
+    /// If the whole buffer has not been uploaded at its current size and format yet, the pixels
+    /// outside the rectangle are undefined until it is.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the rectangle does not fit inside the buffer, or if the size of `data` does not
+    /// match `width` and `height`.
+    pub fn update_buffer_region<T>(&mut self, data: &[T], x: u32, y: u32, width: u32, height: u32) {
+        self.upload_region(data, x, y, width, height);
+        self.redraw();
+    }
+
+    /// Replaces one sprite in a sprite sheet (atlas) that has already been uploaded with
+    /// [`update_buffer`][Framebuffer::update_buffer], without re-uploading the rest of the sheet.
+    ///
+    /// `sprite` contains `sprite_width * sprite_height` pixels in the current buffer format, and
+    /// is copied into the buffer with its first pixel at `(atlas_x, atlas_y)`. Coordinates and row
+    /// order are the same as in the buffer you passed to `update_buffer`, so this works the same
+    /// way regardless of [`inverted_y`][Framebuffer::inverted_y].
+    ///
+    /// This does not draw anything, so that you can update several sprites at once. Call
+    /// [`redraw`][Framebuffer::redraw] when you're done.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the sprite does not fit inside the buffer at the given position, or if the size
+    /// of `sprite` does not match `sprite_width` and `sprite_height`.
+    pub fn update_sprite<T>(
+        &mut self,
+        atlas_x: u32,
+        atlas_y: u32,
+        sprite: &[T],
+        sprite_width: u32,
+        sprite_height: u32,
+    ) {
+        self.upload_region(sprite, atlas_x, atlas_y, sprite_width, sprite_height);
+    }
+
+    fn upload_region<T>(&mut self, data: &[T], x: u32, y: u32, width: u32, height: u32) {
+        self.warn_if_not_current("Updating");
+        let fits_x = matches!(
+            x.checked_add(width),
+            Some(right) if right <= self.buffer_size.width as u32
+        );
+        let fits_y = matches!(
+            y.checked_add(height),
+            Some(top) if top <= self.buffer_size.height as u32
+        );
+        if !fits_x || !fits_y {
+            panic!(
+                "A {}x{} region at ({}, {}) does not fit inside the {}x{} buffer",
+                width,
+                height,
+                x,
+                y,
+                self.buffer_size.width,
+                self.buffer_size.height
+            );
+        }
+
+        let (format, kind) = self.internal.texture_format;
+        let row_length = self.internal.row_length;
         let expected_size_in_bytes = size_of_gl_type_enum(kind)
             * format.components()
-            * self.buffer_size.width as usize
-            * self.buffer_size.height as usize;
-        let actual_size_in_bytes = size_of_val(image_data);
-        if actual_size_in_bytes != expected_size_in_bytes {
+            * strided_pixel_count(row_length, width, height);
+        let actual_size_in_bytes = size_of_val(data);
+        let wrong_size = match row_length {
+            Some(_) => actual_size_in_bytes < expected_size_in_bytes,
+            None => actual_size_in_bytes != expected_size_in_bytes,
+        };
+        if wrong_size {
             panic!(
-                "Expected a buffer of {} bytes, instead recieved one of {} bytes",
+                "Expected a buffer of {} bytes, instead received one of {} bytes",
                 expected_size_in_bytes,
                 actual_size_in_bytes
             );
         }
-        self.draw(|fb| {
-            unsafe {
+        let allocation = Some((self.buffer_size, self.internal.texture_format));
+        let internal_format = self.texture_internal_format(kind);
+        let kind = transfer_type(format, kind);
+        unsafe {
+            gl::BindTexture(gl::TEXTURE_2D, self.internal.texture);
+            if self.internal.texture_allocation != allocation {
+                // there is no storage to copy into yet
                 gl::TexImage2D(
                     gl::TEXTURE_2D,
                     0,
-                    gl::RGBA as _,
-                    fb.buffer_size.width,
-                    fb.buffer_size.height,
+                    internal_format as _,
+                    self.buffer_size.width,
+                    self.buffer_size.height,
                     0,
                     format as GLenum,
                     kind,
-                    image_data.as_ptr() as *const _,
+                    std::ptr::null(),
                 );
+                self.internal.texture_allocation = allocation;
+            }
+            self.internal.has_content = true;
+            gl::PixelStorei(gl::UNPACK_ROW_LENGTH, row_length.unwrap_or(0) as _);
+            gl::TexSubImage2D(
+                gl::TEXTURE_2D,
+                0,
+                x as _,
+                y as _,
+                width as _,
+                height as _,
+                format as GLenum,
+                kind,
+                data.as_ptr() as *const _,
+            );
+            gl::PixelStorei(gl::UNPACK_ROW_LENGTH, 0);
+            if self.internal.mipmaps {
+                gl::GenerateMipmap(gl::TEXTURE_2D);
             }
-        })
+            gl::BindTexture(gl::TEXTURE_2D, 0);
+        }
+    }
+
+    /// Uploads a [`BufferUpload`] that was prepared elsewhere (usually on another thread).
+    ///
+    /// The buffer format and size are changed to match the upload, so the upload does not need to
+    /// agree with whatever was drawn before. The pixel data is handed back afterwards so that its
+    /// allocation can be sent back to the producer and reused for the next frame.
+    pub fn upload<T: ToGlType>(&mut self, upload: BufferUpload<T>) -> Vec<T> {
+        self.change_buffer_format::<T>(upload.format);
+        self.resize_buffer(upload.width, upload.height);
+        self.update_buffer(&upload.data);
+        upload.data
     }
 
-    pub fn use_vertex_shader(&mut self, source: &str) {
-        rebuild_shader(&mut self.internal.vertex_shader, gl::VERTEX_SHADER, source);
-        self.relink_program();
+    /// Replaces the vertex shader.
+    ///
+    /// If the shader fails to compile or the program fails to link, the error is returned as
+    /// [`MglfbError::Shader`] (including the driver's info log) and the previous shader stays in
+    /// use, so a typo never leaves you without a working program. The same goes for the other
+    /// `use_*_shader` methods, and the whole shader API returns [`MglfbError`] like this, so
+    /// loading shaders from files can fail the same way as compiling them.
+    pub fn use_vertex_shader(&mut self, source: &str) -> Result<(), MglfbError> {
+        self.replace_user_shader(gl::VERTEX_SHADER, source)
     }
 
-    pub fn use_fragment_shader(&mut self, source: &str) {
-        rebuild_shader(&mut self.internal.fragment_shader, gl::FRAGMENT_SHADER, source);
-        self.relink_program();
+    pub fn use_fragment_shader(&mut self, source: &str) -> Result<(), MglfbError> {
+        self.replace_user_shader(gl::FRAGMENT_SHADER, source)
     }
 
-    pub fn use_post_process_shader(&mut self, source: &str) {
+    pub fn use_post_process_shader(&mut self, source: &str) -> Result<(), MglfbError> {
         let source = make_post_process_shader(source);
-        self.use_fragment_shader(&source);
+        self.use_fragment_shader(&source)
+    }
+
+    pub fn use_geometry_shader(&mut self, source: &str) -> Result<(), MglfbError> {
+        self.replace_user_shader(gl::GEOMETRY_SHADER, source)
+    }
+
+    /// Reads a complete fragment shader from a file and uses it. Like
+    /// [`use_fragment_shader`][Framebuffer::use_fragment_shader], the previous shader is kept if
+    /// the new one doesn't work.
+    ///
+    /// With the `notify` feature enabled, [`ShaderWatcher`][crate::hot_reload::ShaderWatcher] can
+    /// call this again whenever the file changes.
+    pub fn use_fragment_shader_from_file<P: AsRef<Path>>(
+        &mut self,
+        path: P,
+    ) -> Result<(), MglfbError> {
+        let source = std::fs::read_to_string(path)?;
+        self.use_fragment_shader(&source)
+    }
+
+    /// Reads a ShaderToy style `main_image` function from a file and uses it. See
+    /// [`use_post_process_shader`][Framebuffer::use_post_process_shader] and
+    /// [`use_fragment_shader_from_file`][Framebuffer::use_fragment_shader_from_file].
+    pub fn use_post_process_shader_from_file<P: AsRef<Path>>(
+        &mut self,
+        path: P,
+    ) -> Result<(), MglfbError> {
+        let source = std::fs::read_to_string(path)?;
+        self.use_post_process_shader(&source)
+    }
+
+    /// Like [`replace_shader`][Framebuffer::replace_shader], for shaders that come from the user.
+    /// Those may generate the picture themselves, so from then on the buffer is drawn even if
+    /// nothing has been uploaded.
+    fn replace_user_shader(&mut self, kind: GLenum, source: &str) -> Result<(), MglfbError> {
+        self.replace_shader(kind, source)?;
+        self.internal.has_content = true;
+        Ok(())
+    }
+
+    /// Compiles `source` into the shader slot for `kind` and relinks, restoring the previous
+    /// shader if anything goes wrong.
+    fn replace_shader(&mut self, kind: GLenum, source: &str) -> Result<(), MglfbError> {
+        let new_shader = rustic_gl::raw::create_shader(kind, source)?;
+        let old_shader = self.shader_slot(kind).replace(new_shader);
+
+        match self.relink_program() {
+            Ok(()) => {
+                if let Some(old_shader) = old_shader {
+                    unsafe {
+                        gl::DeleteShader(old_shader);
+                    }
+                }
+                Ok(())
+            }
+            Err(err) => {
+                *self.shader_slot(kind) = old_shader;
+                unsafe {
+                    gl::DeleteShader(new_shader);
+                }
+                Err(err)
+            }
+        }
     }
 
-    pub fn use_geometry_shader(&mut self, source: &str) {
-        rebuild_shader(&mut self.internal.geometry_shader, gl::GEOMETRY_SHADER, source);
-        self.relink_program();
+    fn shader_slot(&mut self, kind: GLenum) -> &mut Option<GLuint> {
+        match kind {
+            gl::VERTEX_SHADER => &mut self.internal.vertex_shader,
+            gl::FRAGMENT_SHADER => &mut self.internal.fragment_shader,
+            gl::GEOMETRY_SHADER => &mut self.internal.geometry_shader,
+            _ => panic!("Must pass a GL enum representing a shader type"),
+        }
     }
 
     pub fn use_grayscale_shader(&mut self) {
-        self.use_fragment_shader(include_str!("./grayscale_fragment_shader.glsl"));
+        self.use_builtin_shader(BuiltinShader::Grayscale);
+    }
+
+    pub fn use_luminance_shader(&mut self) {
+        self.use_builtin_shader(BuiltinShader::Luminance);
+    }
+
+    /// Switches to indexed color: the buffer format becomes [`BufferFormat::R`] with `u8`
+    /// components, and each byte of the buffer picks one of the 256 RGBA colors in `palette`.
+    ///
+    /// The palette is uploaded as a 256x1 texture on [`PALETTE_TEXTURE_UNIT`] and sampled as
+    /// `u_palette`, and the fragment shader is replaced with one that looks the indices up in it.
+    /// This compiles a shader, so only call it once; to change the palette afterwards (for palette
+    /// cycling, for instance), use [`set_palette`][Framebuffer::set_palette].
+    ///
+    /// The indices must reach the shader unchanged, so don't combine this with
+    /// [`set_srgb`][Framebuffer::set_srgb].
+    pub fn use_palette(&mut self, palette: &[[u8; 4]; 256]) {
+        self.change_buffer_format::<u8>(BufferFormat::R);
+        self.set_palette(palette);
+        self.set_uniform_i32("u_palette", PALETTE_TEXTURE_UNIT as i32);
+        let source = include_str!("./palette_fragment_shader.glsl");
+        if let Err(err) = self.replace_shader(gl::FRAGMENT_SHADER, source) {
+            panic!("Built-in shader failed to compile: {}", err);
+        }
+    }
+
+    /// Replaces the palette set with [`use_palette`][Framebuffer::use_palette]. Only the 1KiB
+    /// palette texture is uploaded; the buffer and the shader stay as they are. Takes effect on the
+    /// next draw.
+    pub fn set_palette(&mut self, palette: &[[u8; 4]; 256]) {
+        let palette: Vec<u8> = palette.iter().flatten().copied().collect();
+        self.set_secondary_texture(PALETTE_TEXTURE_UNIT, &palette, 256, 1, BufferFormat::RGBA);
+    }
+
+    /// Replaces the fragment shader with one of the shaders that come with the library. See
+    /// [`BuiltinShader`] for what's available.
+    ///
+    /// Only the fragment shader is replaced; any custom vertex or geometry shader stays in use.
+    pub fn use_builtin_shader(&mut self, shader: BuiltinShader) {
+        let colormap_source;
+        let source = match shader {
+            BuiltinShader::Default => include_str!("./default_fragment_shader.glsl"),
+            BuiltinShader::Grayscale => include_str!("./grayscale_fragment_shader.glsl"),
+            BuiltinShader::Luminance => include_str!("./luminance_fragment_shader.glsl"),
+            BuiltinShader::Colormap(colormap) => {
+                colormap_source = format!(
+                    "{}\n{}",
+                    include_str!("./colormap_fragment_shader.glsl"),
+                    colormap.source()
+                );
+                &colormap_source
+            }
+            BuiltinShader::InvertY => include_str!("./invert_y_fragment_shader.glsl"),
+        };
+        // built-in shaders all sample the buffer, so they don't count as content of their own
+        if let Err(err) = self.replace_shader(gl::FRAGMENT_SHADER, source) {
+            panic!("Built-in shader failed to compile: {}", err);
+        }
     }
 
     pub fn change_buffer_format<T: ToGlType>(
@@ -481,107 +1956,1512 @@ impl Framebuffer {
         format: BufferFormat,
     ) {
         self.internal.texture_format = (format, T::to_gl_enum());
+        self.set_texture_swizzle(IDENTITY_SWIZZLE);
     }
 
-    pub fn resize_buffer(&mut self, buffer_width: u32, buffer_height: u32) {
-        self.buffer_size = LogicalSize::new(buffer_width, buffer_height).cast();
+    /// Changes the buffer format to the one described by `P`, then updates the buffer.
+    ///
+    /// Single component pixels (`u8`) are shown in grayscale without needing a different shader.
+    /// If you would rather choose the format yourself, use
+    /// [`change_buffer_format`][Framebuffer::change_buffer_format] and
+    /// [`update_buffer`][Framebuffer::update_buffer] instead.
+    pub fn show<P: IntoPixels>(&mut self, pixels: &[P]) {
+        if let Err(err) = self.try_show(pixels) {
+            panic!("{}", err);
+        }
     }
 
-    pub fn resize_viewport(&mut self, width: u32, height: u32) {
-        self.vp_size = PhysicalSize::new(width, height).cast();
+    /// The same as [`show`][Framebuffer::show], but returns an error instead of panicking if the
+    /// number of pixels doesn't match the size of the buffer, checked like
+    /// [`try_update_buffer`][Framebuffer::try_update_buffer] does, so a
+    /// [`row_length`][Framebuffer::set_row_length] is honored. The format is left alone in that
+    /// case.
+    pub fn try_show<P: IntoPixels>(&mut self, pixels: &[P]) -> Result<(), BufferSizeError> {
+        // catches `IntoPixels` implementations whose format doesn't describe the type
+        let () = PixelSizeCheck::<P>::OK;
+        let row_length = self.internal.row_length;
+        self.check_buffer_size(std::mem::size_of::<P>(), size_of_val(pixels), row_length)?;
+        self.change_buffer_format::<P::Component>(P::FORMAT);
+        if P::FORMAT == BufferFormat::R {
+            // spread the only component across the color channels so that it shows up as gray
+            self.set_texture_swizzle([gl::RED, gl::RED, gl::RED, gl::ONE]);
+        }
+        self.try_update_buffer(pixels)
     }
 
-    pub fn redraw(&mut self) {
-        self.draw(|_| {})
+    fn set_texture_swizzle(&mut self, swizzle: [GLenum; 4]) {
+        if self.internal.texture_swizzle == swizzle {
+            return;
+        }
+        unsafe {
+            gl::BindTexture(gl::TEXTURE_2D, self.internal.texture);
+            gl::TexParameteriv(
+                gl::TEXTURE_2D,
+                gl::TEXTURE_SWIZZLE_RGBA,
+                swizzle.as_ptr() as *const GLint,
+            );
+            gl::BindTexture(gl::TEXTURE_2D, 0);
+        }
+        self.internal.texture_swizzle = swizzle;
     }
 
-    /// Draw the quad to the active context. Optionally issue other commands after binding
-    /// everything but before drawing it.
-    ///
-    /// You probably want [`redraw`][Framebuffer::redraw] (equivalent to `.draw(|_| {})`).
-    pub fn draw<F: FnOnce(&Framebuffer)>(&mut self, f: F) {
+    /// Changes what custom shaders get when sampling the buffer outside of the `[0, 1]` UV range,
+    /// for instance when reading neighboring pixels at the border or tiling the buffer on purpose.
+    /// The default is [`WrapMode::Clamp`].
+    pub fn set_wrap_mode(&mut self, wrap_mode: WrapMode) {
         unsafe {
-            gl::Viewport(0, 0, self.vp_size.width, self.vp_size.height);
-            gl::UseProgram(self.internal.program);
-            gl::BindVertexArray(self.internal.vao);
-            gl::ActiveTexture(0);
             gl::BindTexture(gl::TEXTURE_2D, self.internal.texture);
-            f(self);
-            gl::DrawArrays(gl::TRIANGLES, 0, 6);
+            set_texture_wrap(wrap_mode);
             gl::BindTexture(gl::TEXTURE_2D, 0);
-            gl::BindVertexArray(0);
-            gl::UseProgram(0);
         }
-        self.did_draw = true;
+        self.internal.wrap_mode = wrap_mode;
     }
 
-    pub fn relink_program(&mut self) {
+    /// Turns mipmapping on or off, for buffers that are shown a lot smaller than they are, like
+    /// thumbnails. Sampling the buffer with `NEAREST` skips most of its pixels when it is scaled
+    /// down, which aliases badly; with mipmaps, the buffer is drawn from prefiltered smaller
+    /// versions of itself (`LINEAR_MIPMAP_LINEAR`) instead. Scaling up is unaffected and stays
+    /// sharp.
+    ///
+    /// The mipmaps are regenerated (`glGenerateMipmap`) after every update of the buffer, which
+    /// costs about a third of the buffer's size in extra memory and some GPU time on every upload.
+    /// Only turn this on if the buffer is actually shown at less than about half its size.
+    pub fn enable_mipmaps(&mut self, enable: bool) {
+        if self.internal.mipmaps == enable {
+            return;
+        }
+        self.internal.mipmaps = enable;
         unsafe {
-            gl::DeleteProgram(self.internal.program);
-            self.internal.program = build_program(&[
-                self.internal.vertex_shader.clone(),
-                self.internal.fragment_shader.clone(),
-                self.internal.geometry_shader.clone(),
-            ]);
+            gl::BindTexture(gl::TEXTURE_2D, self.internal.texture);
+            set_texture_filter(enable);
+            // without mipmaps the texture would be incomplete until the next upload
+            if enable && self.internal.texture_allocation.is_some() {
+                gl::GenerateMipmap(gl::TEXTURE_2D);
+            }
+            gl::BindTexture(gl::TEXTURE_2D, 0);
         }
     }
-}
 
-#[derive(Copy, Clone, PartialEq, Eq, Debug)]
-#[repr(u32)]
-pub enum BufferFormat {
-    R = gl::RED,
-    RG = gl::RG,
-    RGB = gl::RGB,
-    BGR = gl::BGR,
-    RGBA = gl::RGBA,
-    BGRA = gl::BGRA,
-}
+    pub fn resize_buffer(&mut self, buffer_width: u32, buffer_height: u32) {
+        self.buffer_size = LogicalSize::new(buffer_width, buffer_height).cast();
+    }
 
-impl BufferFormat {
-    fn components(&self) -> usize {
-        use self::BufferFormat::*;
-        match self {
-            R => 1,
-            RG => 2,
-            RGB | BGR => 3,
-            RGBA | BGRA => 4,
+    /// Resizes the buffer, keeping what was last uploaded. Pixels keep their coordinates, so the
+    /// contents stay anchored to the buffer's origin; whatever doesn't fit anymore is cut off, and
+    /// new space is filled with `fill`, which is a single pixel in the current buffer format (for
+    /// instance `&[0u8, 0, 0, 255]` for RGBA bytes).
+    ///
+    /// This allocates a new texture and copies the old one into it on the GPU, so unlike
+    /// [`resize_buffer`][Framebuffer::resize_buffer], [`redraw`][Framebuffer::redraw] keeps
+    /// working afterwards without uploading a whole new buffer.
+    ///
+    /// Buffers of `i16` components can't be copied this way, since their texture format can't be
+    /// rendered to; they are filled completely instead.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `fill` is not exactly one pixel in the current buffer format.
+    pub fn resize_buffer_preserving<T>(&mut self, buffer_width: u32, buffer_height: u32, fill: &[T]) {
+        let (format, kind) = self.internal.texture_format;
+        let pixel_size = size_of_gl_type_enum(kind) * format.components();
+        if size_of_val(fill) != pixel_size {
+            panic!(
+                "Expected a fill pixel of {} bytes, instead received one of {} bytes",
+                pixel_size,
+                size_of_val(fill)
+            );
         }
-    }
-}
 
-pub trait ToGlType {
-    fn to_gl_enum() -> GLenum;
-}
+        let new_size = LogicalSize::new(buffer_width, buffer_height).cast::<i32>();
+        let fill = unsafe { std::slice::from_raw_parts(fill.as_ptr() as *const u8, pixel_size) };
+        let fill_data = fill.repeat(buffer_width as usize * buffer_height as usize);
 
-macro_rules! impl_ToGlType {
-    (
-        $(
-            $t:ty, $gl_type:expr
-        ),+,
-    ) => {
-        $(
-            impl ToGlType for $t {
-                fn to_gl_enum() -> GLenum {
-                    $gl_type
+        let old_texture = self.internal.texture;
+        let texture = create_texture();
+        unsafe {
+            gl::BindTexture(gl::TEXTURE_2D, texture);
+            if self.internal.texture_swizzle != IDENTITY_SWIZZLE {
+                gl::TexParameteriv(
+                    gl::TEXTURE_2D,
+                    gl::TEXTURE_SWIZZLE_RGBA,
+                    self.internal.texture_swizzle.as_ptr() as *const GLint,
+                );
+            }
+            set_texture_wrap(self.internal.wrap_mode);
+            set_texture_filter(self.internal.mipmaps);
+            gl::TexImage2D(
+                gl::TEXTURE_2D,
+                0,
+                self.texture_internal_format(kind) as _,
+                new_size.width,
+                new_size.height,
+                0,
+                format as GLenum,
+                transfer_type(format, kind),
+                fill_data.as_ptr() as *const _,
+            );
+
+            // only copy if the old texture actually holds a buffer in the current format
+            if let Some((old_size, old_format)) = self.internal.texture_allocation {
+                if old_format == self.internal.texture_format {
+                    let mut previous_read_framebuffer = 0;
+                    gl::GetIntegerv(gl::READ_FRAMEBUFFER_BINDING, &mut previous_read_framebuffer);
+
+                    let mut framebuffer = 0;
+                    gl::GenFramebuffers(1, &mut framebuffer);
+                    gl::BindFramebuffer(gl::READ_FRAMEBUFFER, framebuffer);
+                    gl::FramebufferTexture2D(
+                        gl::READ_FRAMEBUFFER,
+                        gl::COLOR_ATTACHMENT0,
+                        gl::TEXTURE_2D,
+                        old_texture,
+                        0,
+                    );
+                    if gl::CheckFramebufferStatus(gl::READ_FRAMEBUFFER) == gl::FRAMEBUFFER_COMPLETE {
+                        gl::CopyTexSubImage2D(
+                            gl::TEXTURE_2D,
+                            0,
+                            0,
+                            0,
+                            0,
+                            0,
+                            old_size.width.min(new_size.width),
+                            old_size.height.min(new_size.height),
+                        );
+                    }
+                    gl::BindFramebuffer(gl::READ_FRAMEBUFFER, previous_read_framebuffer as GLuint);
+                    gl::DeleteFramebuffers(1, &framebuffer);
                 }
             }
-        )+
+
+            if self.internal.mipmaps {
+                gl::GenerateMipmap(gl::TEXTURE_2D);
+            }
+            gl::BindTexture(gl::TEXTURE_2D, 0);
+            gl::DeleteTextures(1, &old_texture);
+        }
+
+        self.internal.texture = texture;
+        self.buffer_size = new_size;
+        self.internal.texture_allocation = Some((new_size, self.internal.texture_format));
     }
-}
 
-impl_ToGlType!(
+    /// Turns sRGB handling on or off. When it's on, 8-bit buffers are decoded from sRGB to linear
+    /// when they're sampled, and whatever the shaders output is encoded back to sRGB when it's
+    /// written to the window, so blending and filtering happen in linear space like they should.
+    ///
+    /// The window needs an sRGB-capable framebuffer for this, which is what
+    /// [`Config::srgb`][crate::Config::srgb] asks for. Turning this on or off throws away the
+    /// current texture, so upload the buffer again afterwards. Buffers with more than 8 bits per
+    /// component are always treated as linear.
+    pub fn set_srgb(&mut self, srgb: bool) {
+        if self.internal.srgb == srgb {
+            return;
+        }
+        unsafe {
+            if srgb {
+                gl::Enable(gl::FRAMEBUFFER_SRGB);
+            } else {
+                gl::Disable(gl::FRAMEBUFFER_SRGB);
+            }
+        }
+        self.internal.srgb = srgb;
+        // the texture has to be allocated again with the new internal format
+        self.internal.texture_allocation = None;
+    }
+
+    /// The internal format for the buffer texture, taking sRGB into account.
+    fn texture_internal_format(&self, kind: GLenum) -> GLenum {
+        match kind {
+            gl::UNSIGNED_BYTE if self.internal.srgb => gl::SRGB8_ALPHA8,
+            _ => internal_format_of_gl_type_enum(kind),
+        }
+    }
+
+    /// Returns the size of the buffer in pixels, as (width, height). This is the same as the
+    /// `buffer_size` field, just as a plain tuple of `u32`s like the other sizes this crate takes
+    /// and returns. Change it with [`resize_buffer`][Framebuffer::resize_buffer].
+    pub fn buffer_size(&self) -> (u32, u32) {
+        (self.buffer_size.width as u32, self.buffer_size.height as u32)
+    }
+
+    pub fn buffer_len(&self) -> usize {
+        let (format, _) = self.internal.texture_format;
+        self.buffer_size.width as usize * self.buffer_size.height as usize * format.components()
+    }
+
+    /// The OpenGL name of the program the buffer is currently drawn with, for custom passes that
+    /// want to set its state themselves. Unlike the fields of [`FramebufferInternal`], this stays.
+    ///
+    /// It changes when the program is switched or relinked (see
+    /// [`use_program`][Framebuffer::use_program]), so ask for it again rather than keeping it.
+    /// Anything you change in OpenGL's state through it is yours to restore; the framebuffer
+    /// assumes its own bindings are unchanged between calls.
+    pub fn gl_program(&self) -> GLuint {
+        self.internal.program
+    }
+
+    /// The OpenGL name of the texture the buffer is uploaded into (bound to texture unit 0 when
+    /// drawing). Like [`gl_program`][Framebuffer::gl_program], it's safe to rely on, and
+    /// restoring any state you change through it is up to you.
+    ///
+    /// [`resize_buffer_preserving`][Framebuffer::resize_buffer_preserving] replaces the texture
+    /// with a new one, so ask for it again after that rather than keeping it.
+    pub fn gl_texture(&self) -> GLuint {
+        self.internal.texture
+    }
+
+    /// Sets the size of the viewport. Zero sizes (which some platforms report for minimized
+    /// windows) are ignored, since there would be nothing to draw to.
+    ///
+    /// This does **not** resize the OpenGL context, which has to happen too when the window is
+    /// resized, otherwise the buffer is drawn stretched or cut off on some platforms. If you're
+    /// using a [`GlutinBreakout`], call [`GlutinBreakout::resize_viewport`] instead, which does both.
+    /// Only call this directly to change the viewport without the window changing size.
+    pub fn resize_viewport(&mut self, width: u32, height: u32) {
+        if width == 0 || height == 0 {
+            return;
+        }
+        self.vp_size = PhysicalSize::new(width, height).cast();
+    }
+
+    /// Sets the area of the window that the buffer is drawn to, in physical pixels, with `(x, y)`
+    /// being the bottom left corner. This allows drawing several framebuffers side by side in one
+    /// window.
+    pub fn set_viewport_rect(&mut self, x: i32, y: i32, width: u32, height: u32) {
+        self.vp_position = PhysicalPosition::new(x, y);
+        self.resize_viewport(width, height);
+    }
+
+    pub fn redraw(&mut self) {
+        if !self.internal.has_content {
+            unsafe {
+                gl::BindFramebuffer(gl::DRAW_FRAMEBUFFER, self.internal.target_framebuffer);
+                self.fill_viewport(self.background_color);
+            }
+            return;
+        }
+        self.draw(|_| {})
+    }
+
+    /// Changes the color the window is filled with until the first buffer is uploaded (or a custom
+    /// shader is used, see [`background_color`][Framebuffer::background_color]). Takes effect on
+    /// the next draw.
+    pub fn set_background_color(&mut self, color: [f32; 4]) {
+        self.background_color = color;
+    }
+
+    /// Makes every draw clear the viewport to `color` (RGBA, components from 0 to 1) before
+    /// drawing the buffer, or stops doing so if it is `None`, the default. Takes effect on the next
+    /// draw.
+    ///
+    /// The buffer normally covers the whole viewport, so this only shows where it doesn't: around
+    /// custom geometry, or through transparent pixels drawn with a [`BlendMode`] other than
+    /// [`BlendMode::Replace`]. Without it, those show whatever the window contained before, which
+    /// after a resize or a buffer swap may be garbage. In [`ScaleMode::AspectFit`], the
+    /// [`letterbox_color`][Framebuffer::letterbox_color] is used instead.
+    pub fn set_clear_color(&mut self, color: Option<[f32; 4]>) {
+        self.clear_color = color;
+    }
+
+    /// Changes how the buffer is fitted into the viewport. Takes effect on the next draw.
+    pub fn set_scale_mode(&mut self, scale_mode: ScaleMode) {
+        self.scale_mode = scale_mode;
+    }
+
+    /// Changes the color of the borders drawn in [`ScaleMode::AspectFit`]. Takes effect on the
+    /// next draw.
+    pub fn set_letterbox_color(&mut self, color: [f32; 4]) {
+        self.letterbox_color = color;
+    }
+
+    /// Changes how the buffer is composited onto the window. Takes effect on the next draw.
+    ///
+    /// Blending happens against whatever the window already contains, so this is mostly useful
+    /// when drawing several framebuffers into one window (see
+    /// [`set_viewport_rect`][Framebuffer::set_viewport_rect]). Clear the window first (see
+    /// [`clear`][Framebuffer::clear]) unless the previous frame should show through; its contents
+    /// after a buffer swap are not guaranteed.
+    pub fn set_blend_mode(&mut self, blend_mode: BlendMode) {
+        self.blend_mode = blend_mode;
+    }
+
+    /// Declares whether the buffer's colors are premultiplied by alpha, so that blending weighs
+    /// them correctly. Takes effect on the next draw.
+    ///
+    /// Blending always leaves premultiplied colors in the window, which is what compositors expect
+    /// of [`transparent`][crate::Config::transparent] windows. [`BlendMode::Replace`] doesn't blend
+    /// though, and copies the buffer as-is, so a transparent window drawn that way needs a
+    /// premultiplied buffer whatever this is set to.
+    pub fn set_alpha_mode(&mut self, alpha_mode: AlphaMode) {
+        self.alpha_mode = alpha_mode;
+    }
+
+    /// The part of the viewport the buffer is actually drawn in, as the position of its bottom left
+    /// corner and its size in physical pixels. This is the whole viewport unless the
+    /// [`scale_mode`][Framebuffer::scale_mode] adds borders.
+    pub fn content_viewport(&self) -> (PhysicalPosition<i32>, PhysicalSize<i32>) {
+        match self.scale_mode {
+            ScaleMode::Stretch => (self.vp_position, self.vp_size),
+            ScaleMode::AspectFit => {
+                let buffer_width = self.buffer_size.width.max(1) as f64;
+                let buffer_height = self.buffer_size.height.max(1) as f64;
+                let scale = (self.vp_size.width as f64 / buffer_width)
+                    .min(self.vp_size.height as f64 / buffer_height);
+                let size = PhysicalSize::new(
+                    (buffer_width * scale).round() as i32,
+                    (buffer_height * scale).round() as i32,
+                );
+                let position = PhysicalPosition::new(
+                    self.vp_position.x + (self.vp_size.width - size.width) / 2,
+                    self.vp_position.y + (self.vp_size.height - size.height) / 2,
+                );
+                (position, size)
+            }
+        }
+    }
+
+    /// Converts a position in the window, as reported by `CursorMoved` events, into buffer
+    /// coordinates. This is how [`BasicInput::mouse_pos`] is computed, so see there for how to
+    /// turn the result into an index into the buffer.
+    ///
+    /// Window positions are measured from the top left of the window, and refer to the pixel
+    /// whose top left corner they are on; the result is the center of that pixel. The buffer's
+    /// origin is the bottom left if [`inverted_y`][Framebuffer::inverted_y] is `true`, otherwise
+    /// the top left. Positions outside of the area the buffer is drawn in give coordinates outside
+    /// of the buffer.
+    ///
+    /// `window_height` is the height of the window in physical pixels, which is only needed to
+    /// flip y and may differ from the viewport's.
+    ///
+    /// ```
+    /// # use mini_gl_fb::{get_fancy, config};
+    /// # use mini_gl_fb::glutin::event_loop::EventLoop;
+    /// use mini_gl_fb::glutin::dpi::{LogicalSize, PhysicalPosition};
+    /// # let mut fb = get_fancy(config! {
+    /// #     buffer_size: Some(LogicalSize::new(4, 3))
+    /// # }, &EventLoop::new()).internal.fb;
+    /// let top_left = PhysicalPosition::new(0.0, 0.0);
+    /// let bottom_right = PhysicalPosition::new(7.0, 5.0);
+    ///
+    /// // a 4x3 buffer in an 8x6 window: two window pixels per buffer pixel
+    /// fb.resize_buffer(4, 3);
+    /// fb.resize_viewport(8, 6);
+    /// fb.inverted_y = false;
+    /// assert_eq!(fb.window_to_buffer(top_left, 6), (0.25, 0.25));
+    /// assert_eq!(fb.window_to_buffer(bottom_right, 6), (3.75, 2.75));
+    /// fb.inverted_y = true;
+    /// assert_eq!(fb.window_to_buffer(top_left, 6), (0.25, 2.75));
+    /// assert_eq!(fb.window_to_buffer(bottom_right, 6), (3.75, 0.25));
+    ///
+    /// // an 8x6 buffer in a 4x3 window: two buffer pixels per window pixel
+    /// fb.resize_buffer(8, 6);
+    /// fb.resize_viewport(4, 3);
+    /// let bottom_right = PhysicalPosition::new(3.0, 2.0);
+    /// fb.inverted_y = false;
+    /// assert_eq!(fb.window_to_buffer(top_left, 3), (1.0, 1.0));
+    /// assert_eq!(fb.window_to_buffer(bottom_right, 3), (7.0, 5.0));
+    /// fb.inverted_y = true;
+    /// assert_eq!(fb.window_to_buffer(top_left, 3), (1.0, 5.0));
+    /// assert_eq!(fb.window_to_buffer(bottom_right, 3), (7.0, 1.0));
+    /// ```
+    pub fn window_to_buffer(&self, position: PhysicalPosition<f64>, window_height: u32) -> (f64, f64) {
+        let content = self.window_to_content(position, window_height);
+        let (_, content_size) = self.content_viewport();
+        let x_scale = self.buffer_size.width as f64 / content_size.width as f64;
+        let y_scale = self.buffer_size.height as f64 / content_size.height as f64;
+        (
+            content.x * x_scale,
+            // use the OpenGL texture coordinate system instead of window coordinates
+            if self.inverted_y {
+                content.y * y_scale
+            } else {
+                self.buffer_size.height as f64 - content.y * y_scale
+            }
+        )
+    }
+
+    /// The inverse of [`window_to_buffer`][Framebuffer::window_to_buffer].
+    pub(crate) fn buffer_to_window(
+        &self,
+        position: (f64, f64),
+        window_height: u32,
+    ) -> PhysicalPosition<f64> {
+        let (content_position, content_size) = self.content_viewport();
+        let x_scale = self.buffer_size.width as f64 / content_size.width as f64;
+        let y_scale = self.buffer_size.height as f64 / content_size.height as f64;
+        let (x, y) = position;
+        let content_x = x / x_scale;
+        let content_y = if self.inverted_y {
+            y / y_scale
+        } else {
+            (self.buffer_size.height as f64 - y) / y_scale
+        };
+        PhysicalPosition::new(
+            content_x + content_position.x as f64 - 0.5,
+            window_height as f64 - content_y - content_position.y as f64 - 0.5,
+        )
+    }
+
+    /// Converts a position in the window into physical pixels from the bottom left of the area the
+    /// buffer is drawn in, like [`mouse_position`][Framebuffer::mouse_position].
+    pub(crate) fn window_to_content(
+        &self,
+        position: PhysicalPosition<f64>,
+        window_height: u32,
+    ) -> PhysicalPosition<f64> {
+        let (content_position, _) = self.content_viewport();
+        // winit reports the top left corner of the pixel under the cursor; use its center so that
+        // flipping y doesn't land on the pixel below
+        let x = position.x + 0.5 - content_position.x as f64;
+        let y = window_height as f64 - (position.y + 0.5) - content_position.y as f64;
+        PhysicalPosition::new(x, y)
+    }
+
+    /// Fills the whole window with a solid color (`glClear`), without touching the buffer.
+    ///
+    /// This clears what has been rendered, not the buffer texture: the next
+    /// [`redraw`][Framebuffer::redraw] or [`update_buffer`][Framebuffer::update_buffer] draws the
+    /// buffer over it again. It also doesn't swap buffers, so nothing shows up until you do. The
+    /// color is RGBA with components from 0 to 1.
+    ///
+    /// This always clears the window's default framebuffer, including the parts outside of this
+    /// `Framebuffer`'s viewport.
+    pub fn clear(&mut self, color: [f32; 4]) {
+        let [r, g, b, a] = color;
+        unsafe {
+            gl::BindFramebuffer(gl::DRAW_FRAMEBUFFER, self.internal.target_framebuffer);
+            gl::ClearColor(r, g, b, a);
+            gl::Clear(gl::COLOR_BUFFER_BIT);
+        }
+    }
+
+    /// Queries the format of the window's color buffer, as actually provided by the driver.
+    ///
+    /// See [`ColorFormat`] for why this is useful.
+    pub fn color_format(&self) -> ColorFormat {
+        let attachment = if self.internal.target_framebuffer == 0 {
+            gl::BACK_LEFT
+        } else {
+            gl::COLOR_ATTACHMENT0
+        };
+        let query = |parameter: GLenum| unsafe {
+            let mut value = 0;
+            gl::GetFramebufferAttachmentParameteriv(
+                gl::DRAW_FRAMEBUFFER,
+                attachment,
+                parameter,
+                &mut value,
+            );
+            value
+        };
+
+        unsafe {
+            gl::BindFramebuffer(gl::DRAW_FRAMEBUFFER, self.internal.target_framebuffer);
+        }
+
+        ColorFormat {
+            red_bits: query(gl::FRAMEBUFFER_ATTACHMENT_RED_SIZE) as u8,
+            green_bits: query(gl::FRAMEBUFFER_ATTACHMENT_GREEN_SIZE) as u8,
+            blue_bits: query(gl::FRAMEBUFFER_ATTACHMENT_BLUE_SIZE) as u8,
+            alpha_bits: query(gl::FRAMEBUFFER_ATTACHMENT_ALPHA_SIZE) as u8,
+            srgb: query(gl::FRAMEBUFFER_ATTACHMENT_COLOR_ENCODING) as GLenum == gl::SRGB,
+        }
+    }
+
+    /// Describes the current OpenGL context: which driver and version it is, and what the window's
+    /// color buffer turned out to be. See [`ContextInfo`].
+    pub fn context_info(&self) -> ContextInfo {
+        let string = |name: GLenum| unsafe {
+            let string = gl::GetString(name);
+            if string.is_null() {
+                String::new()
+            } else {
+                CStr::from_ptr(string as *const _).to_string_lossy().into_owned()
+            }
+        };
+
+        // binds the target framebuffer, which the sample count is queried from
+        let color = self.color_format();
+        let mut samples = 0;
+        unsafe {
+            gl::GetIntegerv(gl::SAMPLES, &mut samples);
+        }
+
+        ContextInfo {
+            version: string(gl::VERSION),
+            glsl_version: string(gl::SHADING_LANGUAGE_VERSION),
+            renderer: string(gl::RENDERER),
+            vendor: string(gl::VENDOR),
+            samples: samples.max(0) as u16,
+            color,
+        }
+    }
+
+    /// Reads back what has been drawn to the viewport, as tightly packed 8-bit RGBA pixels.
+    ///
+    /// The rows are in the same order as the buffer passed to
+    /// [`update_buffer`][Framebuffer::update_buffer]: if [`inverted_y`][Framebuffer::inverted_y] is
+    /// `true` (the default) the first row is the bottom of the viewport, otherwise it's the top.
+    /// The result has `vp_size.width * vp_size.height` pixels, which is the size of the window and
+    /// not of the buffer, so scaling is included.
+    ///
+    /// Pixels are read from the framebuffer currently bound for reading, which for a normal window
+    /// is the back buffer. Its contents are undefined after a buffer swap, so read it after drawing
+    /// but before swapping. [`MiniGlFb::screenshot`][crate::MiniGlFb::screenshot] takes care of
+    /// this for you.
+    ///
+    /// Colors read from a transparent window, or after blending, are usually premultiplied by
+    /// their alpha. Pass `true` for `unpremultiply` to convert them to straight alpha with
+    /// [`unpremultiply_alpha`], which is what image files expect; pass `false` to get the pixels
+    /// exactly as they are stored.
+    pub fn read_pixels(&self, unpremultiply: bool) -> Vec<u8> {
+        let width = self.vp_size.width.max(0) as usize;
+        let height = self.vp_size.height.max(0) as usize;
+        let mut pixels = vec![0u8; width * height * 4];
+
+        unsafe {
+            gl::BindFramebuffer(gl::READ_FRAMEBUFFER, self.internal.target_framebuffer);
+            // So there is no padding between rows
+            gl::PixelStorei(gl::PACK_ALIGNMENT, 1);
+            gl::ReadPixels(
+                self.vp_position.x,
+                self.vp_position.y,
+                width as _,
+                height as _,
+                gl::RGBA,
+                gl::UNSIGNED_BYTE,
+                pixels.as_mut_ptr() as *mut _,
+            );
+        }
+
+        // OpenGL reads from the bottom up
+        if !self.inverted_y {
+            flip_rows(&mut pixels, width * 4);
+        }
+        if unpremultiply {
+            unpremultiply_alpha(&mut pixels);
+        }
+
+        pixels
+    }
+
+    /// Blocks until all OpenGL commands issued so far have completed (`glFinish`).
+    ///
+    /// This is mostly useful for timing how long the GPU takes to do something: call it before
+    /// stopping your timer, otherwise you'll only measure how long it took to queue the commands.
+    /// It stalls the CPU until the GPU catches up and throws away any overlap between the two, so
+    /// avoid it outside of benchmarks and debugging.
+    pub fn finish(&self) {
+        unsafe {
+            gl::Finish();
+        }
+    }
+
+    /// Asks the driver to start executing all OpenGL commands issued so far, without waiting for
+    /// them to complete (`glFlush`).
+    pub fn flush(&self) {
+        unsafe {
+            gl::Flush();
+        }
+    }
+
+    /// Using a framebuffer while another window's context is current silently uploads to or draws
+    /// into that window instead. Contexts made current behind our back (with glutin's own
+    /// `make_current`) can't be told apart from mistakes, so this only warns, in debug builds.
+    fn warn_if_not_current(&self, action: &str) {
+        let id = self.internal.context_id;
+        if cfg!(debug_assertions) && id != 0 && CURRENT_CONTEXT.with(Cell::get) != id {
+            log::warn!(
+                "{} a Framebuffer while another window's OpenGL context seems to be current, so it \
+                goes to the wrong window. Call GlutinBreakout::make_current first, or use \
+                GlutinBreakout::update_buffer.",
+                action
+            );
+        }
+    }
+
+    /// Clears the viewport to `color`, but leaves anything outside of it alone, like other tiles of
+    /// a [`TiledView`][crate::tiled::TiledView]. The target framebuffer must be bound.
+    unsafe fn fill_viewport(&self, [r, g, b, a]: [f32; 4]) {
+        gl::Enable(gl::SCISSOR_TEST);
+        gl::Scissor(
+            self.vp_position.x,
+            self.vp_position.y,
+            self.vp_size.width,
+            self.vp_size.height
+        );
+        gl::ClearColor(r, g, b, a);
+        gl::Clear(gl::COLOR_BUFFER_BIT);
+        gl::Disable(gl::SCISSOR_TEST);
+    }
+
+    /// Draw the quad to the active context. Optionally issue other commands after binding
+    /// everything but before drawing it.
+    ///
+    /// You probably want [`redraw`][Framebuffer::redraw] (equivalent to `.draw(|_| {})`).
+    pub fn draw<F: FnOnce(&Framebuffer)>(&mut self, f: F) {
+        self.warn_if_not_current("Drawing");
+        let (content_position, content_size) = self.content_viewport();
+        unsafe {
+            gl::BindFramebuffer(gl::DRAW_FRAMEBUFFER, self.internal.target_framebuffer);
+            let fill = if content_size != self.vp_size {
+                Some(self.letterbox_color)
+            } else {
+                self.clear_color
+            };
+            if let Some(color) = fill {
+                self.fill_viewport(color);
+            }
+            gl::Viewport(
+                content_position.x,
+                content_position.y,
+                content_size.width,
+                content_size.height
+            );
+            gl::UseProgram(self.internal.program);
+            gl::Uniform2f(
+                self.internal.resolution_location,
+                content_size.width as f32,
+                content_size.height as f32,
+            );
+            gl::Uniform1f(
+                self.internal.time_location,
+                self.internal.start_time.elapsed().as_secs_f32(),
+            );
+            gl::Uniform2f(
+                self.internal.mouse_location,
+                self.mouse_position.x as f32,
+                self.mouse_position.y as f32,
+            );
+            self.apply_uniforms();
+            gl::BindVertexArray(self.internal.vao);
+            for (&unit, &texture) in &self.internal.secondary_textures {
+                gl::ActiveTexture(gl::TEXTURE0 + unit);
+                gl::BindTexture(gl::TEXTURE_2D, texture);
+            }
+            // `f` may expect the buffer texture to be bound to the active unit
+            gl::ActiveTexture(gl::TEXTURE0);
+            gl::BindTexture(gl::TEXTURE_2D, self.internal.texture);
+            // premultiplied colors already carry their alpha
+            let src_color = match self.alpha_mode {
+                AlphaMode::Straight => gl::SRC_ALPHA,
+                AlphaMode::Premultiplied => gl::ONE,
+            };
+            match self.blend_mode {
+                BlendMode::Replace => gl::Disable(gl::BLEND),
+                BlendMode::AlphaBlend => {
+                    gl::Enable(gl::BLEND);
+                    gl::BlendFuncSeparate(
+                        src_color,
+                        gl::ONE_MINUS_SRC_ALPHA,
+                        gl::ONE,
+                        gl::ONE_MINUS_SRC_ALPHA,
+                    );
+                }
+                BlendMode::Additive => {
+                    gl::Enable(gl::BLEND);
+                    gl::BlendFunc(src_color, gl::ONE);
+                }
+            }
+            f(self);
+            gl::DrawArrays(gl::TRIANGLES, 0, 6);
+            gl::Disable(gl::BLEND);
+            for &unit in self.internal.secondary_textures.keys() {
+                gl::ActiveTexture(gl::TEXTURE0 + unit);
+                gl::BindTexture(gl::TEXTURE_2D, 0);
+            }
+            gl::ActiveTexture(gl::TEXTURE0);
+            gl::BindTexture(gl::TEXTURE_2D, 0);
+            gl::BindVertexArray(0);
+            gl::UseProgram(0);
+
+            // Catch invalid calls while developing. This also reports errors caused by GL calls
+            // made in `f`, or by the user since the last draw, so it only logs rather than
+            // panicking over something that may not be our fault
+            if self.internal.gl_debug || cfg!(debug_assertions) {
+                // each call normally returns one pending error, but without a working context some
+                // drivers keep returning the same one forever
+                let mut errors = 0;
+                loop {
+                    let error = gl::GetError();
+                    if error == gl::NO_ERROR {
+                        break;
+                    }
+                    if errors == MAX_REPORTED_GL_ERRORS {
+                        log::error!("Further OpenGL errors while drawing suppressed");
+                        break;
+                    }
+                    log::error!("OpenGL error 0x{:x} while drawing", error);
+                    errors += 1;
+                }
+            }
+        }
+        self.did_draw = true;
+    }
+
+    /// Links the current shaders into a new program. If linking fails the old program is kept, and
+    /// the [`MglfbError::Shader`] error carries the driver's link log, which says for instance which
+    /// outputs of one stage don't match the inputs of the next.
+    ///
+    /// The new program is used from then on, even if a [registered
+    /// program][Framebuffer::register_program] was in use before.
+    pub fn relink_program(&mut self) -> Result<(), MglfbError> {
+        unsafe {
+            let program = build_program(&[
+                self.internal.vertex_shader,
+                self.internal.fragment_shader,
+                self.internal.geometry_shader,
+            ])?;
+            self.delete_program(self.internal.linked_program);
+            self.internal.linked_program = program;
+            self.switch_program(program);
+        }
+        Ok(())
+    }
+
+    /// Compiles and links a whole program ahead of time, so that
+    /// [`use_program`][Framebuffer::use_program] can switch to it later without compiling
+    /// anything. This is for switching between effects often, even every frame, which would be
+    /// slow with the `use_*_shader` methods since they relink every time.
+    ///
+    /// `vertex` defaults to the built-in vertex shader, which passes `v_uv` on to the fragment
+    /// shader. The fragment shader has to be complete, like for
+    /// [`use_fragment_shader`][Framebuffer::use_fragment_shader]. Registering another program
+    /// under the same name replaces it and keeps its handle.
+    ///
+    /// Uniforms set with [`set_uniform`][Framebuffer::set_uniform] apply to whichever program is in
+    /// use.
+    pub fn register_program(
+        &mut self,
+        name: &str,
+        vertex: Option<&str>,
+        fragment: &str,
+        geometry: Option<&str>,
+    ) -> Result<ProgramHandle, MglfbError> {
+        let vertex = vertex.unwrap_or(include_str!("./default_vertex_shader.glsl"));
+        let mut shaders = Vec::new();
+        let sources = [
+            (gl::VERTEX_SHADER, Some(vertex)),
+            (gl::FRAGMENT_SHADER, Some(fragment)),
+            (gl::GEOMETRY_SHADER, geometry),
+        ];
+        let mut result = Ok(());
+        for &(kind, source) in &sources {
+            if let Some(source) = source {
+                match rustic_gl::raw::create_shader(kind, source) {
+                    Ok(shader) => shaders.push(Some(shader)),
+                    Err(err) => {
+                        result = Err(err);
+                        break;
+                    }
+                }
+            }
+        }
+        let program = result.and_then(|()| unsafe { build_program(&shaders) });
+        // the program keeps what it needs from the shaders
+        for shader in shaders.into_iter().flatten() {
+            unsafe { gl::DeleteShader(shader); }
+        }
+        let program = program?;
+
+        let owner = self.internal.framebuffer_id;
+        let programs = &mut self.internal.programs;
+        let handle = match programs.iter().position(|(existing, _)| existing == name) {
+            Some(index) => {
+                let old_program = std::mem::replace(&mut programs[index].1, program);
+                if self.internal.program == old_program {
+                    unsafe { self.switch_program(program); }
+                }
+                unsafe { self.delete_program(old_program); }
+                ProgramHandle { owner, index }
+            }
+            None => {
+                programs.push((name.to_string(), program));
+                ProgramHandle { owner, index: programs.len() - 1 }
+            }
+        };
+        Ok(handle)
+    }
+
+    /// Looks up a program registered with [`register_program`][Framebuffer::register_program] by
+    /// name.
+    pub fn program_handle(&self, name: &str) -> Option<ProgramHandle> {
+        self.internal.programs
+            .iter()
+            .position(|(existing, _)| existing == name)
+            .map(|index| ProgramHandle { owner: self.internal.framebuffer_id, index })
+    }
+
+    /// Switches to a program registered with
+    /// [`register_program`][Framebuffer::register_program]. This only changes which program is
+    /// used for drawing, so it is cheap. Takes effect on the next draw.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the handle comes from another `Framebuffer`.
+    pub fn use_program(&mut self, handle: ProgramHandle) {
+        let program = match self.internal.programs.get(handle.index) {
+            Some(&(_, program)) if handle.owner == self.internal.framebuffer_id => program,
+            _ => panic!("{:?} was not registered with this Framebuffer", handle),
+        };
+        unsafe { self.switch_program(program); }
+        self.internal.has_content = true;
+    }
+
+    /// Switches back from a registered program to the one built from the shaders set with the
+    /// `use_*_shader` methods, without relinking it.
+    pub fn use_linked_program(&mut self) {
+        unsafe { self.switch_program(self.internal.linked_program); }
+    }
+
+    /// Makes `program` the one that is drawn with, looking up the uniforms that are set on every
+    /// draw.
+    unsafe fn switch_program(&mut self, program: GLuint) {
+        self.internal.program = program;
+        self.internal.sampler_location = buffer_sampler_location(program);
+        let (resolution, time, mouse) = builtin_uniform_locations(program);
+        self.internal.resolution_location = resolution;
+        self.internal.time_location = time;
+        self.internal.mouse_location = mouse;
+    }
+
+    /// Deletes `program` along with its cached uniform locations, since OpenGL may hand its name
+    /// out again.
+    unsafe fn delete_program(&mut self, program: GLuint) {
+        gl::DeleteProgram(program);
+        self.internal.uniform_locations.retain(|(owner, _), _| *owner != program);
+    }
+
+    /// Sets a `float` uniform in the shaders. The value is kept and applied every time the buffer
+    /// is drawn, until it is changed; it takes effect on the next draw.
+    ///
+    /// Uniforms that the shaders don't declare (or that the compiler optimized out because they
+    /// aren't used) are ignored. The type has to match the declaration in the shader, otherwise
+    /// OpenGL reports an error when drawing.
+    pub fn set_uniform_f32(&mut self, name: &str, value: f32) {
+        self.set_uniform(name, UniformValue::F32(value));
+    }
+
+    /// Sets a `vec2` uniform in the shaders. See [`set_uniform_f32`][Framebuffer::set_uniform_f32].
+    pub fn set_uniform_vec2(&mut self, name: &str, value: [f32; 2]) {
+        self.set_uniform(name, UniformValue::Vec2(value));
+    }
+
+    /// Sets a `vec4` uniform in the shaders. See [`set_uniform_f32`][Framebuffer::set_uniform_f32].
+    pub fn set_uniform_vec4(&mut self, name: &str, value: [f32; 4]) {
+        self.set_uniform(name, UniformValue::Vec4(value));
+    }
+
+    /// Sets an `int` uniform in the shaders. See [`set_uniform_f32`][Framebuffer::set_uniform_f32].
+    pub fn set_uniform_i32(&mut self, name: &str, value: i32) {
+        self.set_uniform(name, UniformValue::I32(value));
+    }
+
+    /// Sets a uniform of any supported type in the shaders, which is what the typed `set_uniform_*`
+    /// methods call. This is handy when the values come from somewhere else, like a settings file
+    /// or a UI, and their types are only known at runtime. Otherwise it behaves exactly like
+    /// [`set_uniform_f32`][Framebuffer::set_uniform_f32]: the value is kept and applied on every
+    /// draw, and setting a name again replaces its value, even with one of a different type.
+    pub fn set_uniform(&mut self, name: &str, value: UniformValue) {
+        self.internal.uniforms.insert(name.to_string(), value);
+    }
+
+    /// Uploads an extra texture that shaders can sample alongside the buffer, for instance a
+    /// palette lookup table or an overlay.
+    ///
+    /// The texture is bound to texture unit `unit` and is available to shaders as
+    /// `uniform sampler2D u_buffer<unit>;` (so `u_buffer1` for unit 1), which your shader has to
+    /// declare itself. `data` contains the components of `width * height` pixels in the given
+    /// format, one after another with no padding. `T` is the type of each component, like in
+    /// [`change_buffer_format`][Framebuffer::change_buffer_format]. Calling this again with the
+    /// same unit replaces the texture.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `unit` is 0, which is always the main buffer, or if the size of `data` does not
+    /// match `width`, `height` and `format`.
+    pub fn set_secondary_texture<T: ToGlType>(
+        &mut self,
+        unit: u32,
+        data: &[T],
+        width: u32,
+        height: u32,
+        format: BufferFormat,
+    ) {
+        assert!(unit != 0, "Texture unit 0 is reserved for the buffer");
+        let kind = T::to_gl_enum();
+        let expected_size_in_bytes = size_of_gl_type_enum(kind)
+            * format.components()
+            * width as usize
+            * height as usize;
+        let actual_size_in_bytes = size_of_val(data);
+        if actual_size_in_bytes != expected_size_in_bytes {
+            panic!(
+                "Expected a buffer of {} bytes, instead received one of {} bytes",
+                expected_size_in_bytes,
+                actual_size_in_bytes
+            );
+        }
+
+        let texture = *self.internal.secondary_textures.entry(unit).or_insert_with(create_texture);
+        unsafe {
+            gl::BindTexture(gl::TEXTURE_2D, texture);
+            gl::TexImage2D(
+                gl::TEXTURE_2D,
+                0,
+                internal_format_of_gl_type_enum(kind) as _,
+                width as _,
+                height as _,
+                0,
+                format as GLenum,
+                transfer_type(format, kind),
+                data.as_ptr() as *const _,
+            );
+            gl::BindTexture(gl::TEXTURE_2D, 0);
+        }
+        self.set_uniform_i32(&format!("u_buffer{}", unit), unit as i32);
+    }
+
+    /// Removes a texture added with [`set_secondary_texture`][Framebuffer::set_secondary_texture].
+    pub fn remove_secondary_texture(&mut self, unit: u32) {
+        if let Some(texture) = self.internal.secondary_textures.remove(&unit) {
+            unsafe {
+                gl::DeleteTextures(1, &texture);
+            }
+            self.internal.uniforms.remove(&format!("u_buffer{}", unit));
+        }
+    }
+
+    /// Uploads the values of all uniforms set with `set_uniform`. The program must be in use.
+    fn apply_uniforms(&mut self) {
+        let program = self.internal.program;
+        let locations = &mut self.internal.uniform_locations;
+        for (name, value) in &self.internal.uniforms {
+            let location = *locations.entry((program, name.clone())).or_insert_with(|| {
+                match CString::new(name.as_str()) {
+                    Ok(name) => unsafe { gl::GetUniformLocation(program, name.as_ptr()) },
+                    // can't possibly be a valid uniform name
+                    Err(_) => -1,
+                }
+            });
+            unsafe {
+                match *value {
+                    UniformValue::F32(value) => gl::Uniform1f(location, value),
+                    UniformValue::Vec2([x, y]) => gl::Uniform2f(location, x, y),
+                    UniformValue::Vec4([x, y, z, w]) => gl::Uniform4f(location, x, y, z, w),
+                    UniformValue::I32(value) => gl::Uniform1i(location, value),
+                }
+            }
+        }
+    }
+}
+
+/// The format of the color buffer that the window is drawn to, as returned by
+/// [`Framebuffer::color_format`].
+///
+/// Depending on the platform, you may be given fewer bits per color than you would expect (16 bit
+/// color is still around on some mobile GPUs), which causes visible banding in smooth gradients.
+/// Checking this lets you detect such a surface and warn about it or adapt to it.
+#[non_exhaustive]
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Debug)]
+pub struct ColorFormat {
+    /// The number of bits used to store the red component of each pixel.
+    pub red_bits: u8,
+    /// The number of bits used to store the green component of each pixel.
+    pub green_bits: u8,
+    /// The number of bits used to store the blue component of each pixel.
+    pub blue_bits: u8,
+    /// The number of bits used to store the alpha component of each pixel. This is often 0 when
+    /// the window is not transparent.
+    pub alpha_bits: u8,
+    /// Whether the color buffer is encoded as sRGB rather than linear.
+    pub srgb: bool,
+}
+
+/// What an OpenGL context ended up being, as returned by [`Framebuffer::context_info`].
+///
+/// The [`Config`][crate::Config] only asks for things like a GL version, multisampling and sRGB;
+/// the driver decides what it actually gives you. This is what it decided, which is worth
+/// including in bug reports (its `Debug` output has everything) and lets you scale quality to the
+/// hardware.
+#[non_exhaustive]
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct ContextInfo {
+    /// The OpenGL version string, like `"4.6.0 NVIDIA 535.54.03"`. It always starts with the
+    /// version number; what follows is up to the driver.
+    pub version: String,
+    /// The GLSL version string, like `"4.60 NVIDIA"`.
+    pub glsl_version: String,
+    /// The name of the GPU or software renderer, like `"llvmpipe (LLVM 15.0.6, 256 bits)"`.
+    pub renderer: String,
+    /// The company responsible for the driver.
+    pub vendor: String,
+    /// The number of samples per pixel of the window's color buffer, or 0 without multisampling.
+    pub samples: u16,
+    /// The window's color buffer, including whether it is sRGB. The same as
+    /// [`Framebuffer::color_format`].
+    pub color: ColorFormat,
+}
+
+/// Converts tightly packed RGBA pixels with premultiplied alpha into straight (non-premultiplied)
+/// alpha, in place.
+///
+/// When the window is transparent or things have been blended into it, the colors read back from
+/// it are usually premultiplied by their alpha. Most image formats (PNG in particular) expect
+/// straight alpha instead, and saving premultiplied pixels as-is gives translucent areas dark
+/// fringes. Run pixels through this before exporting them, or pass `true` to
+/// [`Framebuffer::read_pixels`] to have it done for you. Fully transparent pixels are left alone,
+/// since their color can't be recovered.
+///
+/// ```
+/// use mini_gl_fb::core::unpremultiply_alpha;
+///
+/// let mut pixels = [100u8, 50, 0, 128, 10, 20, 30, 0];
+/// unpremultiply_alpha(&mut pixels);
+/// assert_eq!(pixels, [199, 100, 0, 128, 10, 20, 30, 0]);
+/// ```
+pub fn unpremultiply_alpha(pixels: &mut [u8]) {
+    for pixel in pixels.chunks_exact_mut(4) {
+        let alpha = pixel[3] as u32;
+        if alpha == 0 || alpha == 255 {
+            continue;
+        }
+        for component in &mut pixel[..3] {
+            *component = ((*component as u32 * 255 + alpha / 2) / alpha).min(255) as u8;
+        }
+    }
+}
+
+/// A buffer of pixel data along with everything needed to upload it to a [`Framebuffer`].
+///
+/// Unlike a `Framebuffer`, a `BufferUpload` makes no OpenGL calls, so it can be created and filled
+/// on any thread and then sent to the thread that owns the OpenGL context, where it is passed to
+/// [`Framebuffer::upload`]. The `Framebuffer` itself cannot be sent to other threads, so the
+/// compiler makes sure the actual upload happens in the right place.
+///
+/// ```
+/// use mini_gl_fb::BufferFormat;
+/// use mini_gl_fb::core::BufferUpload;
+/// use std::sync::mpsc::channel;
+/// use std::thread;
+///
+/// let (sender, receiver) = channel();
+///
+/// thread::spawn(move || {
+///     let mut upload = BufferUpload::<u8>::new(800, 600, BufferFormat::RGBA);
+///     for pixel in upload.data.chunks_exact_mut(4) {
+///         pixel.copy_from_slice(&[128, 0, 0, 255]);
+///     }
+///     sender.send(upload).unwrap();
+/// });
+///
+/// let upload = receiver.recv().unwrap();
+/// assert_eq!(upload.data.len(), 800 * 600 * 4);
+/// // ...then, on the thread that owns the context: `fb.upload(upload);`
+/// ```
+#[non_exhaustive]
+#[derive(Clone, PartialEq, Debug)]
+pub struct BufferUpload<T> {
+    /// The width of the buffer in pixels.
+    pub width: u32,
+    /// The height of the buffer in pixels.
+    pub height: u32,
+    /// The format of each pixel in [`data`][BufferUpload::data].
+    pub format: BufferFormat,
+    /// The components of every pixel, tightly packed, in the same order that
+    /// [`update_buffer`][Framebuffer::update_buffer] expects.
+    pub data: Vec<T>,
+}
+
+impl<T: ToGlType + Default + Clone> BufferUpload<T> {
+    /// Creates a new upload of the given size and format, with every component set to its default
+    /// value (zero).
+    pub fn new(width: u32, height: u32, format: BufferFormat) -> Self {
+        let len = width as usize * height as usize * format.components();
+        Self::from_vec(width, height, format, vec![T::default(); len])
+    }
+}
+
+impl<T: ToGlType> BufferUpload<T> {
+    /// Wraps existing pixel data (for instance, a `Vec` that was handed back by
+    /// [`Framebuffer::upload`]) in a new upload.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the length of `data` does not match the size and format of the buffer.
+    pub fn from_vec(width: u32, height: u32, format: BufferFormat, data: Vec<T>) -> Self {
+        let expected = width as usize * height as usize * format.components();
+        if data.len() != expected {
+            panic!(
+                "Expected a buffer of {} components, instead received one of {} components",
+                expected,
+                data.len()
+            );
+        }
+        BufferUpload { width, height, format, data }
+    }
+}
+
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+#[repr(u32)]
+pub enum BufferFormat {
+    R = gl::RED,
+    RG = gl::RG,
+    RGB = gl::RGB,
+    BGR = gl::BGR,
+    RGBA = gl::RGBA,
+    BGRA = gl::BGRA,
+}
+
+impl BufferFormat {
+    /// The 4-component format that the OpenGL driver on this OS is most likely to take as is, so
+    /// that uploading a buffer is a plain copy instead of a conversion. Worth following if you
+    /// produce the pixels yourself and can pick their order, for instance when mirroring the
+    /// screen at full frame rate; for most programs the difference doesn't matter.
+    ///
+    /// This is [`BGRA`][BufferFormat::BGRA] on Windows and macOS, where drivers keep 8-bit
+    /// textures in that order, and [`RGBA`][BufferFormat::RGBA] elsewhere (Mesa stores textures in
+    /// whichever order they were first uploaded in, so both are fast there). [`Bgra`] pixels go
+    /// through the fast path automatically.
+    ///
+    /// ```rust
+    /// use mini_gl_fb::BufferFormat;
+    ///
+    /// let format = BufferFormat::platform_preferred();
+    /// assert!(format == BufferFormat::BGRA || format == BufferFormat::RGBA);
+    /// ```
+    pub const fn platform_preferred() -> BufferFormat {
+        if cfg!(any(target_os = "windows", target_os = "macos")) {
+            BufferFormat::BGRA
+        } else {
+            BufferFormat::RGBA
+        }
+    }
+
+    const fn components(&self) -> usize {
+        use self::BufferFormat::*;
+        match self {
+            R => 1,
+            RG => 2,
+            RGB | BGR => 3,
+            RGBA | BGRA => 4,
+        }
+    }
+}
+
+/// Identifies a program registered with [`Framebuffer::register_program`].
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Debug)]
+pub struct ProgramHandle {
+    owner: u64,
+    index: usize,
+}
+
+/// How the buffer is fitted into the viewport when their sizes differ.
+#[non_exhaustive]
+#[derive(Copy, Clone, PartialEq, Eq, Debug, Default)]
+pub enum ScaleMode {
+    /// The buffer is stretched to fill the whole viewport, even if that distorts it. This is the
+    /// default.
+    #[default]
+    Stretch,
+    /// The buffer is scaled as large as it fits while keeping its aspect ratio, and centered. The
+    /// rest of the viewport is filled with the
+    /// [`letterbox_color`][Framebuffer::letterbox_color].
+    AspectFit,
+}
+
+/// How the buffer is combined with what is already in the window when it is drawn. Anything other
+/// than [`BlendMode::Replace`] uses the alpha channel of the buffer (after any shader).
+#[non_exhaustive]
+#[derive(Copy, Clone, PartialEq, Eq, Debug, Default)]
+pub enum BlendMode {
+    /// The buffer overwrites the window contents, alpha included. This is the default.
+    #[default]
+    Replace,
+    /// Standard "over" compositing: `src * src_alpha + dst * (1 - src_alpha)`.
+    AlphaBlend,
+    /// The buffer is added onto the window contents, weighted by its alpha. Useful for glows and
+    /// light effects.
+    Additive,
+}
+
+/// Whether the colors in the buffer are already multiplied by their alpha. This decides how they
+/// are weighted when blended (see [`BlendMode`]); with [`BlendMode::Replace`] it makes no
+/// difference.
+#[non_exhaustive]
+#[derive(Copy, Clone, PartialEq, Eq, Debug, Default)]
+pub enum AlphaMode {
+    /// The colors are independent of the alpha, so half transparent white is `[255, 255, 255,
+    /// 128]`. Most image decoders produce this. This is the default.
+    #[default]
+    Straight,
+    /// The colors have been multiplied by the alpha, so half transparent white is `[128, 128,
+    /// 128, 128]`. Blending straight colors as if they were premultiplied (or the other way
+    /// around) causes dark or bright fringes around transparent edges.
+    Premultiplied,
+}
+
+/// What the buffer texture returns when a shader samples it outside of the `[0, 1]` UV range.
+#[non_exhaustive]
+#[derive(Copy, Clone, PartialEq, Eq, Debug, Default)]
+pub enum WrapMode {
+    /// Coordinates are clamped, so the edge pixels extend forever. This is the default.
+    #[default]
+    Clamp,
+    /// The buffer is tiled.
+    Repeat,
+    /// The buffer is tiled, with every other copy mirrored so the edges line up.
+    MirroredRepeat,
+}
+
+impl WrapMode {
+    fn to_gl_enum(self) -> GLenum {
+        match self {
+            WrapMode::Clamp => gl::CLAMP_TO_EDGE,
+            WrapMode::Repeat => gl::REPEAT,
+            WrapMode::MirroredRepeat => gl::MIRRORED_REPEAT,
+        }
+    }
+}
+
+/// A value for a custom shader uniform, as set by [`Framebuffer::set_uniform`].
+#[non_exhaustive]
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub enum UniformValue {
+    /// A `float`.
+    F32(f32),
+    /// A `vec2`.
+    Vec2([f32; 2]),
+    /// A `vec4`.
+    Vec4([f32; 4]),
+    /// An `int`, which is also how samplers are pointed at a texture unit.
+    I32(i32),
+}
+
+/// The fragment shaders that ship with the library, for use with
+/// [`Framebuffer::use_builtin_shader`].
+#[non_exhaustive]
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum BuiltinShader {
+    /// Shows the buffer as-is. This is the shader that is used when the framebuffer is created.
+    Default,
+    /// Uses the first component of each pixel for all three colors. Use this with
+    /// [`BufferFormat::R`] to render one byte per pixel in grayscale.
+    Grayscale,
+    /// Converts color buffers to grayscale while preserving perceived brightness, using the Rec.
+    /// 709 luma coefficients.
+    Luminance,
+    /// Maps the first component of each pixel onto a [`Colormap`]. Useful for visualizing
+    /// heightmaps, heatmaps, and other single channel data.
+    Colormap(Colormap),
+    /// Shows the buffer upside down, without having to recreate the framebuffer with a different
+    /// [`Config::invert_y`][crate::Config::invert_y].
+    InvertY,
+}
+
+/// A colormap used by [`BuiltinShader::Colormap`].
+#[non_exhaustive]
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum Colormap {
+    /// Matplotlib's perceptually uniform, colorblind friendly default.
+    Viridis,
+    /// Google's improved rainbow colormap.
+    Turbo,
+    /// Black, through red and yellow, to white.
+    Hot,
+}
+
+impl Colormap {
+    fn source(&self) -> &'static str {
+        match self {
+            Colormap::Viridis => include_str!("./colormaps/viridis.glsl"),
+            Colormap::Turbo => include_str!("./colormaps/turbo.glsl"),
+            Colormap::Hot => include_str!("./colormaps/hot.glsl"),
+        }
+    }
+}
+
+const IDENTITY_SWIZZLE: [GLenum; 4] = [gl::RED, gl::GREEN, gl::BLUE, gl::ALPHA];
+
+/// A type that represents a single pixel, used by [`Framebuffer::show`] to pick the
+/// [`BufferFormat`] automatically.
+///
+/// Since the format comes from the type of the pixels, it can't disagree with them, which is easy
+/// to get wrong when calling [`Framebuffer::change_buffer_format`] and
+/// [`Framebuffer::update_buffer`] separately. Single components and arrays of 2, 3 or 4 components
+/// are R, RG, RGB and RGBA. Wrap pixels in [`Bgr`] or [`Bgra`] for those orders instead.
+///
+/// If you implement this for your own pixel type, it must be laid out exactly like
+/// `[Self::Component; N]` for the `N` components of `FORMAT`, and should be `#[repr(C)]` or
+/// `#[repr(transparent)]`. Types of the wrong size are rejected at compile time.
+pub trait IntoPixels {
+    /// The type of each component of the pixel.
+    type Component: ToGlType;
+    /// The format that a buffer of these pixels should be interpreted as.
+    const FORMAT: BufferFormat;
+}
+
+/// Fails to compile when used with an [`IntoPixels`] type that isn't the size its format says.
+struct PixelSizeCheck<P>(PhantomData<P>);
+
+impl<P: IntoPixels> PixelSizeCheck<P> {
+    const OK: () = assert!(
+        std::mem::size_of::<P>() == std::mem::size_of::<P::Component>() * P::FORMAT.components(),
+        "The size of the pixel type doesn't match its format",
+    );
+}
+
+/// A pixel with its color components stored in the order blue, green, red, for
+/// [`Framebuffer::show`].
+#[repr(transparent)]
+#[derive(Copy, Clone, PartialEq, Debug, Default)]
+pub struct Bgr<T>(pub [T; 3]);
+
+/// A pixel with its components stored in the order blue, green, red, alpha, as in Windows bitmaps,
+/// for [`Framebuffer::show`].
+#[repr(transparent)]
+#[derive(Copy, Clone, PartialEq, Debug, Default)]
+pub struct Bgra<T>(pub [T; 4]);
+
+macro_rules! impl_IntoPixels {
+    ($($t:ty),+) => {
+        $(
+            impl IntoPixels for $t {
+                type Component = $t;
+                const FORMAT: BufferFormat = BufferFormat::R;
+            }
+
+            impl IntoPixels for [$t; 2] {
+                type Component = $t;
+                const FORMAT: BufferFormat = BufferFormat::RG;
+            }
+
+            impl IntoPixels for [$t; 3] {
+                type Component = $t;
+                const FORMAT: BufferFormat = BufferFormat::RGB;
+            }
+
+            impl IntoPixels for [$t; 4] {
+                type Component = $t;
+                const FORMAT: BufferFormat = BufferFormat::RGBA;
+            }
+
+            impl IntoPixels for Bgr<$t> {
+                type Component = $t;
+                const FORMAT: BufferFormat = BufferFormat::BGR;
+            }
+
+            impl IntoPixels for Bgra<$t> {
+                type Component = $t;
+                const FORMAT: BufferFormat = BufferFormat::BGRA;
+            }
+        )+
+    }
+}
+
+impl_IntoPixels!(u8, u16, f32);
+
+pub trait ToGlType {
+    fn to_gl_enum() -> GLenum;
+}
+
+macro_rules! impl_ToGlType {
+    (
+        $(
+            $t:ty, $gl_type:expr
+        ),+,
+    ) => {
+        $(
+            impl ToGlType for $t {
+                fn to_gl_enum() -> GLenum {
+                    $gl_type
+                }
+            }
+        )+
+    }
+}
+
+impl_ToGlType!(
     u8, gl::UNSIGNED_BYTE,
     i8, gl::BYTE,
+    u16, gl::UNSIGNED_SHORT,
+    i16, gl::SHORT,
+    u32, gl::UNSIGNED_INT,
+    i32, gl::INT,
+    f32, gl::FLOAT,
 );
 
+/// The number of pixels read by an upload of `width * height` pixels whose rows are `row_length`
+/// pixels apart, or tightly packed for `None`.
+fn strided_pixel_count(row_length: Option<u32>, width: u32, height: u32) -> usize {
+    match row_length {
+        None => width as usize * height as usize,
+        Some(row_length) => {
+            assert!(
+                row_length >= width,
+                "The row length ({}) is shorter than the rows being uploaded ({})",
+                row_length,
+                width
+            );
+            if height == 0 {
+                0
+            } else {
+                row_length as usize * (height as usize - 1) + width as usize
+            }
+        }
+    }
+}
+
 fn size_of_gl_type_enum(gl_enum: GLenum) -> usize {
     match gl_enum {
         gl::UNSIGNED_BYTE | gl::BYTE => 1,
+        gl::UNSIGNED_SHORT | gl::SHORT => 2,
+        gl::UNSIGNED_INT | gl::INT | gl::FLOAT => 4,
         _ => panic!("Must pass a GL enum representing a type"),
     }
 }
 
+/// The internal format of the buffer texture for a given component type, picked so that no
+/// precision is lost. Integer components are still normalized to 0..1 when sampled, but floats are
+/// not clamped, so HDR buffers can be tone mapped in a shader.
+fn internal_format_of_gl_type_enum(gl_enum: GLenum) -> GLenum {
+    match gl_enum {
+        gl::UNSIGNED_BYTE => gl::RGBA8,
+        gl::BYTE => gl::RGBA,
+        gl::UNSIGNED_SHORT => gl::RGBA16,
+        gl::SHORT => gl::RGBA16_SNORM,
+        gl::UNSIGNED_INT | gl::INT | gl::FLOAT => gl::RGBA32F,
+        _ => panic!("Must pass a GL enum representing a type"),
+    }
+}
+
+/// The type to hand OpenGL when uploading pixels of the given format and component type.
+///
+/// 8-bit BGRA is what most drivers store textures as, but they only recognize it as such (and copy
+/// it straight into the texture) when it's described as packed 32-bit pixels. On little-endian
+/// machines that is the same memory layout as four `u8`s.
+fn transfer_type(format: BufferFormat, kind: GLenum) -> GLenum {
+    match (format, kind) {
+        (BufferFormat::BGRA, gl::UNSIGNED_BYTE) if cfg!(target_endian = "little") => {
+            gl::UNSIGNED_INT_8_8_8_8_REV
+        }
+        _ => kind,
+    }
+}
+
+/// Reverses the order of the rows in an image, in place.
+pub(crate) fn flip_rows(pixels: &mut [u8], row_length: usize) {
+    if row_length == 0 {
+        return;
+    }
+    let height = pixels.len() / row_length;
+    for row in 0..height / 2 {
+        let (top, bottom) = pixels.split_at_mut((height - row - 1) * row_length);
+        top[row * row_length..(row + 1) * row_length].swap_with_slice(&mut bottom[..row_length]);
+    }
+}
+
 fn create_texture() -> GLuint {
     unsafe {
         let mut tex = 0;
@@ -591,13 +3471,54 @@ fn create_texture() -> GLuint {
             panic!();
         }
         gl::BindTexture(gl::TEXTURE_2D, tex);
-        gl::TexParameteri(gl::TEXTURE_2D, gl::TEXTURE_MIN_FILTER, gl::NEAREST as _);
-        gl::TexParameteri(gl::TEXTURE_2D, gl::TEXTURE_MAG_FILTER, gl::NEAREST as _);
+        set_texture_filter(false);
+        set_texture_wrap(WrapMode::Clamp);
         gl::BindTexture(gl::TEXTURE_2D, 0);
         tex
     }
 }
 
+/// Receives `KHR_debug` messages after [`Framebuffer::enable_gl_debug`].
+extern "system" fn log_gl_debug_message(
+    _source: GLenum,
+    _gltype: GLenum,
+    id: GLuint,
+    severity: GLenum,
+    length: GLsizei,
+    message: *const GLchar,
+    _user_param: *mut std::os::raw::c_void,
+) {
+    let message = unsafe {
+        if length < 0 {
+            std::ffi::CStr::from_ptr(message).to_string_lossy()
+        } else {
+            String::from_utf8_lossy(std::slice::from_raw_parts(message as *const u8, length as usize))
+        }
+    };
+    match severity {
+        gl::DEBUG_SEVERITY_HIGH => log::error!("OpenGL (0x{:x}): {}", id, message),
+        gl::DEBUG_SEVERITY_MEDIUM | gl::DEBUG_SEVERITY_LOW => {
+            log::warn!("OpenGL (0x{:x}): {}", id, message)
+        }
+        _ => log::debug!("OpenGL (0x{:x}): {}", id, message),
+    }
+}
+
+/// Sets the filters of the bound texture. The buffer is always magnified with `NEAREST` so that
+/// its pixels stay sharp; minification blends between mipmap levels if they are enabled.
+unsafe fn set_texture_filter(mipmaps: bool) {
+    let min_filter = if mipmaps { gl::LINEAR_MIPMAP_LINEAR } else { gl::NEAREST };
+    gl::TexParameteri(gl::TEXTURE_2D, gl::TEXTURE_MIN_FILTER, min_filter as _);
+    gl::TexParameteri(gl::TEXTURE_2D, gl::TEXTURE_MAG_FILTER, gl::NEAREST as _);
+}
+
+/// Sets the wrap mode of the texture bound to `TEXTURE_2D`.
+unsafe fn set_texture_wrap(wrap_mode: WrapMode) {
+    let wrap = wrap_mode.to_gl_enum() as GLint;
+    gl::TexParameteri(gl::TEXTURE_2D, gl::TEXTURE_WRAP_S, wrap);
+    gl::TexParameteri(gl::TEXTURE_2D, gl::TEXTURE_WRAP_T, wrap);
+}
+
 fn make_post_process_shader(source: &str) -> String {
     format!(
         "
@@ -607,57 +3528,58 @@ fn make_post_process_shader(source: &str) -> String {
 
             out vec4 r_frag_color;
 
-            uniform sampler2D u_buffer;
+            uniform sampler2D {sampler};
+            uniform vec2 u_resolution;
+            uniform float u_time;
+            uniform vec2 u_mouse;
 
-            {}
+            {source}
 
             void main() {{
                 main_image(r_frag_color, v_uv);
             }}
         ",
-        source,
+        sampler = BUFFER_SAMPLER_NAME,
+        source = source,
     )
 }
 
-fn rebuild_shader(shader: &mut Option<GLuint>, kind: GLenum, source: &str) {
-    if let Some(shader) = *shader {
-        unsafe {
-            gl::DeleteShader(shader);
-        }
-    }
-    let compilation_result = rustic_gl::raw::create_shader(kind, source);
-    match compilation_result {
-        Ok(gl_id) => {
-            *shader = Some(gl_id);
-        },
-        Err(rustic_gl::error::GlError::ShaderCompilation(info)) => {
-            if let Some(log) = info {
-                panic!("Shader compilation failed with the following information: {}", log);
-            } else {
-                panic!("Shader compilation failed without any information.")
-            }
-        },
-        Err(err) => {
-            panic!("An error occured while compiling shader: {}", err);
-        }
-    }
+/// Looks up [`BUFFER_SAMPLER_NAME`] and points it at texture unit 0.
+unsafe fn buffer_sampler_location(program: GLuint) -> GLint {
+    let name = CString::new(BUFFER_SAMPLER_NAME).unwrap();
+    let location = gl::GetUniformLocation(program, name.as_ptr());
+    gl::UseProgram(program);
+    gl::Uniform1i(location, 0);
+    gl::UseProgram(0);
+    location
+}
+
+/// Looks up the `u_resolution`, `u_time` and `u_mouse` uniforms that are set on every draw.
+unsafe fn builtin_uniform_locations(program: GLuint) -> (GLint, GLint, GLint) {
+    (
+        gl::GetUniformLocation(program, b"u_resolution\0".as_ptr() as *const _),
+        gl::GetUniformLocation(program, b"u_time\0".as_ptr() as *const _),
+        gl::GetUniformLocation(program, b"u_mouse\0".as_ptr() as *const _),
+    )
 }
 
-unsafe fn build_program(shaders: &[Option<GLuint>]) -> GLuint {
-    let program = rustic_gl::raw::create_program()
-        .unwrap();
+unsafe fn build_program(shaders: &[Option<GLuint>]) -> Result<GLuint, GlError> {
+    let program = rustic_gl::raw::create_program()?;
     for shader in shaders.iter() {
         if let &Some(shader) = shader {
             gl::AttachShader(program, shader);
         }
     }
     gl::LinkProgram(program);
-    rustic_gl::raw::get_link_status(program)
-        .unwrap();
+    let link_status = rustic_gl::raw::get_link_status(program);
     for shader in shaders {
         if let &Some(shader) = shader {
             gl::DetachShader(program, shader);
         }
     }
-    program
+    if let Err(err) = link_status {
+        gl::DeleteProgram(program);
+        return Err(err);
+    }
+    Ok(program)
 }
@@ -0,0 +1,222 @@
+//! Simple shapes drawn straight into a CPU-side buffer, for when you just need to put some lines
+//! on the screen without pulling in a 2D graphics library. Requires the `draw` feature.
+
+/// A buffer to draw shapes into, borrowed for as long as you're drawing.
+///
+/// Coordinates are the same as the indices into the buffer (`y * width + x`), which are also
+/// the coordinates of [`BasicInput::mouse_pos`][crate::BasicInput::mouse_pos]. So with the default
+/// [`Config::invert_y`][crate::Config::invert_y], `(0, 0)` is the bottom left, otherwise the top
+/// left. Use [`flip_y`][Canvas::flip_y] to measure from the other end instead.
+///
+/// Anything outside of the buffer is clipped, so shapes can stick out over the edges. Pixels can be
+/// any type, like `[u8; 4]` for RGBA or `u8` for grayscale.
+///
+/// ```
+/// use mini_gl_fb::draw::Canvas;
+///
+/// let mut buffer = vec![[0u8, 0, 0, 255]; 100 * 100];
+/// let white = [255u8, 255, 255, 255];
+///
+/// let mut canvas = Canvas::new(&mut buffer, 100, 100);
+/// canvas.draw_line((10, 10), (90, 50), white);
+/// canvas.fill_rect(20, 60, 30, 20, white);
+/// canvas.draw_circle((70, 70), 15, white);
+///
+/// assert_eq!(buffer[10 * 100 + 10], white);
+/// ```
+#[derive(Debug)]
+pub struct Canvas<'a, T> {
+    pixels: &'a mut [T],
+    width: u32,
+    height: u32,
+    flip_y: bool,
+}
+
+impl<'a, T: Copy> Canvas<'a, T> {
+    /// Wraps a buffer of `width * height` pixels.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `pixels` doesn't have exactly `width * height` pixels.
+    pub fn new(pixels: &'a mut [T], width: u32, height: u32) -> Self {
+        assert_eq!(
+            pixels.len(),
+            width as usize * height as usize,
+            "Expected a buffer of {}x{} pixels",
+            width,
+            height
+        );
+        Canvas { pixels, width, height, flip_y: false }
+    }
+
+    /// Measures y from the other end of the buffer, so that (with the default
+    /// [`Config::invert_y`][crate::Config::invert_y]) `(0, 0)` is the top left like in most image
+    /// editors.
+    pub fn flip_y(mut self) -> Self {
+        self.flip_y = !self.flip_y;
+        self
+    }
+
+    /// Returns the size of the buffer, as (width, height).
+    pub fn size(&self) -> (u32, u32) {
+        (self.width, self.height)
+    }
+
+    /// Sets a single pixel, unless it is outside of the buffer.
+    pub fn plot(&mut self, x: i32, y: i32, color: T) {
+        if x < 0 || y < 0 || x >= self.width as i32 || y >= self.height as i32 {
+            return;
+        }
+        let y = if self.flip_y { self.height as i32 - 1 - y } else { y };
+        self.pixels[y as usize * self.width as usize + x as usize] = color;
+    }
+
+    /// Draws a one pixel wide line from `start` to `end`, including both ends.
+    pub fn draw_line(&mut self, start: (i32, i32), end: (i32, i32), color: T) {
+        // https://en.wikipedia.org/wiki/Bresenham%27s_line_algorithm
+        let (mut x0, mut y0) = start;
+        let (x1, y1) = end;
+        let dx = (x1 - x0).abs();
+        let sx = if x0 < x1 { 1 } else { -1 };
+        let dy = -(y1 - y0).abs();
+        let sy = if y0 < y1 { 1 } else { -1 };
+        let mut err = dx + dy;
+
+        while x0 != x1 || y0 != y1 {
+            self.plot(x0, y0, color);
+            let e2 = err * 2;
+            if e2 > dy {
+                err += dy;
+                x0 += sx;
+            }
+            if e2 <= dx {
+                err += dx;
+                y0 += sy;
+            }
+        }
+
+        self.plot(x1, y1, color);
+    }
+
+    /// Draws the one pixel wide outline of a `width` by `height` rectangle, with its corner with the
+    /// lowest coordinates at `(x, y)`.
+    pub fn draw_rect(&mut self, x: i32, y: i32, width: u32, height: u32, color: T) {
+        if width == 0 || height == 0 {
+            return;
+        }
+        let right = x + width as i32 - 1;
+        let top = y + height as i32 - 1;
+        self.draw_line((x, y), (right, y), color);
+        self.draw_line((x, top), (right, top), color);
+        self.draw_line((x, y), (x, top), color);
+        self.draw_line((right, y), (right, top), color);
+    }
+
+    /// Fills a `width` by `height` rectangle, with its corner with the lowest coordinates at
+    /// `(x, y)`.
+    pub fn fill_rect(&mut self, x: i32, y: i32, width: u32, height: u32, color: T) {
+        let x0 = x.max(0);
+        let y0 = y.max(0);
+        let x1 = x.saturating_add(width as i32).min(self.width as i32);
+        let y1 = y.saturating_add(height as i32).min(self.height as i32);
+        for y in y0..y1 {
+            for x in x0..x1 {
+                self.plot(x, y, color);
+            }
+        }
+    }
+
+    /// Draws the one pixel wide outline of a circle.
+    pub fn draw_circle(&mut self, center: (i32, i32), radius: u32, color: T) {
+        // https://en.wikipedia.org/wiki/Midpoint_circle_algorithm
+        let (cx, cy) = center;
+        let mut x = radius as i32;
+        let mut y = 0;
+        let mut err = 1 - x;
+
+        while x >= y {
+            // one point in each octant
+            let points = [(x, y), (y, x), (-y, x), (-x, y), (-x, -y), (-y, -x), (y, -x), (x, -y)];
+            for &(dx, dy) in &points {
+                self.plot(cx + dx, cy + dy, color);
+            }
+            y += 1;
+            if err < 0 {
+                err += 2 * y + 1;
+            } else {
+                x -= 1;
+                err += 2 * (y - x) + 1;
+            }
+        }
+    }
+
+    /// Copies a `src_width` by `src_height` sprite into the buffer, with its corner with the
+    /// lowest coordinates at `(x, y)`. The parts that stick out over the edges are left out.
+    ///
+    /// The sprite is laid out like the buffer (`y * src_width + x`), and follows
+    /// [`flip_y`][Canvas::flip_y] too, so a sprite that looks right in the buffer's orientation
+    /// looks right when blitted.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `src` doesn't have exactly `src_width * src_height` pixels.
+    pub fn blit(&mut self, src: &[T], src_width: u32, src_height: u32, x: i32, y: i32) {
+        self.blit_where(src, src_width, src_height, x, y, |_| true);
+    }
+
+    fn blit_where<F>(&mut self, src: &[T], src_width: u32, src_height: u32, x: i32, y: i32, keep: F)
+    where
+        F: Fn(&T) -> bool,
+    {
+        assert_eq!(
+            src.len(),
+            src_width as usize * src_height as usize,
+            "Expected a sprite of {}x{} pixels",
+            src_width,
+            src_height
+        );
+        // only walk the part of the sprite that ends up inside the buffer
+        let x0 = (-x).clamp(0, src_width as i32);
+        let y0 = (-y).clamp(0, src_height as i32);
+        let x1 = (self.width as i32).saturating_sub(x).clamp(0, src_width as i32);
+        let y1 = (self.height as i32).saturating_sub(y).clamp(0, src_height as i32);
+        for sy in y0..y1 {
+            let row = &src[sy as usize * src_width as usize..][..src_width as usize];
+            for sx in x0..x1 {
+                let pixel = row[sx as usize];
+                if keep(&pixel) {
+                    self.plot(x + sx, y + sy, pixel);
+                }
+            }
+        }
+    }
+}
+
+impl<'a> Canvas<'a, [u8; 4]> {
+    /// Like [`blit`][Canvas::blit], but skips the pixels of the sprite that are fully
+    /// transparent, so that only its shape is drawn over what's already there. Other pixels are
+    /// copied as they are, without blending.
+    ///
+    /// ```
+    /// use mini_gl_fb::draw::Canvas;
+    ///
+    /// let black = [0u8, 0, 0, 255];
+    /// let red = [255u8, 0, 0, 255];
+    /// let clear = [0u8; 4];
+    /// let sprite = [
+    ///     clear, red,
+    ///     red, clear,
+    /// ];
+    ///
+    /// let mut buffer = vec![black; 4 * 4];
+    /// let mut canvas = Canvas::new(&mut buffer, 4, 4);
+    /// // sticks out over the right edge
+    /// canvas.blit_keyed(&sprite, 2, 2, 3, 0);
+    ///
+    /// assert_eq!(buffer[3], black);
+    /// assert_eq!(buffer[4 + 3], red);
+    /// ```
+    pub fn blit_keyed(&mut self, src: &[[u8; 4]], src_width: u32, src_height: u32, x: i32, y: i32) {
+        self.blit_where(src, src_width, src_height, x, y, |pixel| pixel[3] != 0);
+    }
+}
@@ -0,0 +1,167 @@
+//! Contains the [`DrawBuffer`] struct, which owns the pixels of a buffer on the CPU side and
+//! uploads only what changed.
+
+use crate::core::{Framebuffer, IntoPixels, ToGlType};
+
+/// A buffer of pixels that remembers which part of it has changed since it was last uploaded.
+///
+/// Most programs keep a `Vec` of pixels next to the [`Framebuffer`], change a few of them and
+/// upload the whole thing again. A `DrawBuffer` does the same, but [`flush`][DrawBuffer::flush]
+/// only uploads the smallest rectangle that contains every pixel changed since the last flush,
+/// which is a lot cheaper when changes are small, like in a paint program.
+///
+/// Coordinates are the same as in the buffer you pass to
+/// [`update_buffer`][Framebuffer::update_buffer], and the same as
+/// [`BasicInput::mouse_pos`][crate::BasicInput::mouse_pos]. The buffer format follows from the
+/// pixel type, like for [`Framebuffer::show`].
+///
+/// ```rust,no_run
+/// use mini_gl_fb::DrawBuffer;
+/// use mini_gl_fb::glutin::event::{MouseButton, VirtualKeyCode};
+///
+/// let (mut event_loop, mut fb) = mini_gl_fb::gotta_go_fast("Paint", 800.0, 600.0);
+/// let mut canvas = DrawBuffer::new(800, 600, [255u8, 255, 255, 255]);
+///
+/// fb.glutin_handle_basic_input(&mut event_loop, |fb, input| {
+///     input.wait = true;
+///     if input.mouse_is_down(MouseButton::Left) {
+///         let (x, y) = input.mouse_pos;
+///         canvas.set_pixel(x as i32, y as i32, [0, 0, 0, 255]);
+///     }
+///     if input.key_pressed(VirtualKeyCode::C) {
+///         canvas.fill([255, 255, 255, 255]);
+///     }
+///     canvas.flush(fb);
+///     !input.key_is_down(VirtualKeyCode::Escape)
+/// });
+/// ```
+#[derive(Clone, Debug)]
+pub struct DrawBuffer<P> {
+    pixels: Vec<P>,
+    width: u32,
+    height: u32,
+    // the changed area as (left, bottom, right, top), with right and top exclusive
+    dirty: Option<(u32, u32, u32, u32)>,
+}
+
+impl<P: IntoPixels + Copy> DrawBuffer<P> {
+    /// Creates a `width` by `height` buffer filled with `fill`. All of it is uploaded on the first
+    /// flush.
+    pub fn new(width: u32, height: u32, fill: P) -> Self {
+        DrawBuffer {
+            pixels: vec![fill; width as usize * height as usize],
+            width,
+            height,
+            dirty: Some((0, 0, width, height)),
+        }
+    }
+
+    /// Returns the size of the buffer, as (width, height).
+    pub fn size(&self) -> (u32, u32) {
+        (self.width, self.height)
+    }
+
+    /// The pixels, `y * width + x` being the index of the pixel at `(x, y)`.
+    pub fn pixels(&self) -> &[P] {
+        &self.pixels
+    }
+
+    /// Gives access to all pixels at once, for changes that don't fit the other methods. All of
+    /// the buffer is uploaded on the next flush, so prefer [`set_pixel`][DrawBuffer::set_pixel]
+    /// or [`mark_dirty`][DrawBuffer::mark_dirty] if you only change a small part of it.
+    pub fn pixels_mut(&mut self) -> &mut [P] {
+        self.mark_dirty(0, 0, self.width, self.height);
+        &mut self.pixels
+    }
+
+    /// Returns the pixel at `(x, y)`, or `None` if that's outside of the buffer.
+    pub fn get_pixel(&self, x: i32, y: i32) -> Option<P> {
+        self.index(x, y).map(|index| self.pixels[index])
+    }
+
+    /// Sets the pixel at `(x, y)`, unless it is outside of the buffer.
+    pub fn set_pixel(&mut self, x: i32, y: i32, pixel: P) {
+        if let Some(index) = self.index(x, y) {
+            self.pixels[index] = pixel;
+            self.mark_dirty(x as u32, y as u32, 1, 1);
+        }
+    }
+
+    /// Sets every pixel.
+    pub fn fill(&mut self, pixel: P) {
+        self.fill_rect(0, 0, self.width, self.height, pixel);
+    }
+
+    /// Sets every pixel in a `width` by `height` rectangle with its corner with the lowest
+    /// coordinates at `(x, y)`. The parts outside of the buffer are left out.
+    pub fn fill_rect(&mut self, x: i32, y: i32, width: u32, height: u32, pixel: P) {
+        let left = x.clamp(0, self.width as i32) as u32;
+        let bottom = y.clamp(0, self.height as i32) as u32;
+        let right = x.saturating_add(width as i32).clamp(0, self.width as i32) as u32;
+        let top = y.saturating_add(height as i32).clamp(0, self.height as i32) as u32;
+        if left >= right || bottom >= top {
+            return;
+        }
+        for row in bottom..top {
+            let start = row as usize * self.width as usize;
+            self.pixels[start + left as usize..start + right as usize].fill(pixel);
+        }
+        self.mark_dirty(left, bottom, right - left, top - bottom);
+    }
+
+    /// Marks a rectangle as changed, so that it is uploaded on the next flush. Only needed after
+    /// changing pixels through something other than this type's methods.
+    pub fn mark_dirty(&mut self, x: u32, y: u32, width: u32, height: u32) {
+        let right = x.saturating_add(width).min(self.width);
+        let top = y.saturating_add(height).min(self.height);
+        if x >= right || y >= top {
+            return;
+        }
+        self.dirty = Some(match self.dirty {
+            None => (x, y, right, top),
+            Some((l, b, r, t)) => (l.min(x), b.min(y), r.max(right), t.max(top)),
+        });
+    }
+
+    /// Whether anything has changed since the last flush.
+    pub fn is_dirty(&self) -> bool {
+        self.dirty.is_some()
+    }
+
+    /// Uploads the pixels that changed since the last flush to `fb` and redraws it, if anything
+    /// changed. Like [`Framebuffer::update_buffer`], this doesn't swap buffers.
+    ///
+    /// If `fb` doesn't hold a buffer of this size and format yet (for instance on the first
+    /// flush, or after the framebuffer was used for something else), it is resized and everything
+    /// is uploaded. Returns whether anything was uploaded.
+    pub fn flush(&mut self, fb: &mut Framebuffer) -> bool {
+        let format = (P::FORMAT, P::Component::to_gl_enum());
+        let size = fb.buffer_size();
+        let current = fb.internal.texture_allocation == Some((fb.buffer_size, format));
+        if size != (self.width, self.height) || !current {
+            fb.resize_buffer(self.width, self.height);
+            fb.show(&self.pixels);
+            self.dirty = None;
+            return true;
+        }
+
+        let (left, bottom, right, top) = match self.dirty.take() {
+            Some(dirty) => dirty,
+            None => return false,
+        };
+        // upload the rectangle straight out of the pixels, without copying it first
+        let previous_row_length = fb.internal.row_length;
+        fb.set_row_length(Some(self.width));
+        let start = bottom as usize * self.width as usize + left as usize;
+        fb.update_buffer_region(&self.pixels[start..], left, bottom, right - left, top - bottom);
+        fb.set_row_length(previous_row_length);
+        true
+    }
+
+    fn index(&self, x: i32, y: i32) -> Option<usize> {
+        if x < 0 || y < 0 || x >= self.width as i32 || y >= self.height as i32 {
+            return None;
+        }
+        Some(y as usize * self.width as usize + x as usize)
+    }
+}
@@ -0,0 +1,148 @@
+//! Errors that can happen while setting up or driving a window.
+
+use glutin::{CreationError, ContextError};
+use glutin::window::BadIcon;
+use glutin::error::ExternalError;
+use rustic_gl::error::GlError;
+
+use crate::core::MIN_GL_VERSION;
+
+use std::fmt;
+use std::io;
+
+/// Everything that can go wrong in `mini_gl_fb`. Most of these just wrap an error from glutin.
+#[non_exhaustive]
+#[derive(Debug)]
+pub enum MglfbError {
+    /// The window or its OpenGL context couldn't be created. This usually means the requested
+    /// OpenGL version isn't supported by the drivers on this machine.
+    Creation(CreationError),
+    /// The OpenGL context was created, but couldn't be made current right after, so nothing can be
+    /// drawn with it. The context is destroyed again.
+    MakeCurrent(ContextError),
+    /// An operation on an existing context failed, for instance swapping buffers.
+    Context(ContextError),
+    /// The OpenGL version requested through [`Config::gl_version`][crate::Config::gl_version] is
+    /// too old for the shaders `mini_gl_fb` uses.
+    UnsupportedGlVersion((u8, u8)),
+    /// [`Config::samples`][crate::Config::samples] is neither 0 nor a power of two.
+    InvalidSamples(u16),
+    /// The platform or driver doesn't let the swap interval be changed after the context has been
+    /// created, or doesn't support the interval that was asked for (adaptive vsync in particular).
+    /// Set [`Config::vsync`][crate::Config::vsync] instead.
+    VsyncUnsupported,
+    /// A shader failed to compile or link. The error contains the driver's info log.
+    Shader(GlError),
+    /// A file (for instance a shader source file) couldn't be read.
+    Io(io::Error),
+    /// A [`WindowIcon`][crate::WindowIcon]'s pixel data doesn't match its size.
+    BadIcon(BadIcon),
+    /// The platform refused to do something with the cursor, like grabbing it.
+    Cursor(ExternalError),
+}
+
+impl fmt::Display for MglfbError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            MglfbError::Creation(err) => write!(f, "failed to create OpenGL context: {}", err),
+            MglfbError::MakeCurrent(err) => {
+                write!(f, "failed to make the new OpenGL context current: {}", err)
+            }
+            MglfbError::Context(err) => write!(f, "OpenGL context error: {}", err),
+            MglfbError::UnsupportedGlVersion((major, minor)) => write!(
+                f,
+                "OpenGL {}.{} was requested, but at least {}.{} is required",
+                major, minor, MIN_GL_VERSION.0, MIN_GL_VERSION.1
+            ),
+            MglfbError::InvalidSamples(samples) => write!(
+                f,
+                "{} samples per pixel were requested, but it must be 0 or a power of two",
+                samples
+            ),
+            MglfbError::VsyncUnsupported => {
+                write!(f, "vsync can't be changed to this at runtime on this platform")
+            }
+            MglfbError::Shader(err) => write!(f, "{}", err),
+            MglfbError::Io(err) => write!(f, "{}", err),
+            MglfbError::BadIcon(err) => write!(f, "invalid window icon: {}", err),
+            MglfbError::Cursor(err) => write!(f, "cursor error: {}", err),
+        }
+    }
+}
+
+impl std::error::Error for MglfbError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            MglfbError::Creation(err) => Some(err),
+            MglfbError::MakeCurrent(err) => Some(err),
+            MglfbError::Context(err) => Some(err),
+            MglfbError::Shader(err) => Some(err),
+            MglfbError::Io(err) => Some(err),
+            MglfbError::BadIcon(err) => Some(err),
+            MglfbError::Cursor(err) => Some(err),
+            MglfbError::UnsupportedGlVersion(_)
+            | MglfbError::InvalidSamples(_)
+            | MglfbError::VsyncUnsupported => None,
+        }
+    }
+}
+
+/// Returned by [`try_update_buffer`][crate::MiniGlFb::try_update_buffer] when the buffer passed in
+/// doesn't have the size the current buffer size and format call for. Both sizes are in bytes.
+#[non_exhaustive]
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub struct BufferSizeError {
+    /// The number of bytes the buffer should have been.
+    pub expected: usize,
+    /// The number of bytes the buffer actually was.
+    pub actual: usize,
+}
+
+impl fmt::Display for BufferSizeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "Expected a buffer of {} bytes, instead received one of {} bytes",
+            self.expected,
+            self.actual
+        )
+    }
+}
+
+impl std::error::Error for BufferSizeError {}
+
+impl From<CreationError> for MglfbError {
+    fn from(err: CreationError) -> Self {
+        MglfbError::Creation(err)
+    }
+}
+
+impl From<ContextError> for MglfbError {
+    fn from(err: ContextError) -> Self {
+        MglfbError::Context(err)
+    }
+}
+
+impl From<GlError> for MglfbError {
+    fn from(err: GlError) -> Self {
+        MglfbError::Shader(err)
+    }
+}
+
+impl From<io::Error> for MglfbError {
+    fn from(err: io::Error) -> Self {
+        MglfbError::Io(err)
+    }
+}
+
+impl From<BadIcon> for MglfbError {
+    fn from(err: BadIcon) -> Self {
+        MglfbError::BadIcon(err)
+    }
+}
+
+impl From<ExternalError> for MglfbError {
+    fn from(err: ExternalError) -> Self {
+        MglfbError::Cursor(err)
+    }
+}
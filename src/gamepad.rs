@@ -0,0 +1,153 @@
+//! Gamepad input for [`BasicInput`][crate::BasicInput], using [`gilrs`]. Requires the `gilrs`
+//! feature.
+//!
+//! With the feature enabled, [`glutin_handle_basic_input`][crate::MiniGlFb::glutin_handle_basic_input]
+//! keeps [`BasicInput::gamepads`][crate::BasicInput::gamepads] up to date:
+//!
+//! ```rust,no_run
+//! use mini_gl_fb::gilrs::{Button, Axis};
+//! use mini_gl_fb::glutin::event::VirtualKeyCode;
+//!
+//! let (mut event_loop, mut fb) = mini_gl_fb::gotta_go_fast("Gamepads", 800.0, 600.0);
+//! let mut x = 400.0;
+//!
+//! fb.glutin_handle_basic_input(&mut event_loop, |_fb, input| {
+//!     if let Some(pad) = input.gamepads.iter().find(|pad| pad.connected) {
+//!         x += pad.axis(Axis::LeftStickX) as f64 * 200.0 * input.dt.as_secs_f64();
+//!         if pad.button_pressed(Button::South) {
+//!             println!("Jump!");
+//!         }
+//!     }
+//!     !input.key_is_down(VirtualKeyCode::Escape)
+//! });
+//! ```
+
+use gilrs::{Gilrs, GamepadId, Button, Axis, EventType};
+
+use std::collections::HashMap;
+use std::time::Duration;
+
+/// How often gamepads are checked for input while [`BasicInput::wait`][crate::BasicInput::wait]
+/// is set. Gamepad input doesn't wake up the event loop by itself.
+pub(crate) const POLL_INTERVAL: Duration = Duration::from_millis(10);
+
+/// The state of one gamepad, in the style of [`BasicInput::keys`][crate::BasicInput::keys].
+#[non_exhaustive]
+#[derive(Clone, PartialEq, Debug)]
+pub struct GamepadState {
+    /// Identifies the gamepad to [`gilrs`]. A gamepad that is unplugged and plugged back in keeps
+    /// its id.
+    pub id: GamepadId,
+    /// The name of the gamepad, as reported by the system or the mapping.
+    pub name: String,
+    /// `false` after the gamepad was disconnected. Gamepads aren't removed from
+    /// [`BasicInput::gamepads`][crate::BasicInput::gamepads] when they are disconnected, so that
+    /// the indices of the others don't change; if it is reconnected, this becomes `true` again.
+    pub connected: bool,
+    /// Stores whether a button was down and is down, in that order.
+    ///
+    /// If a button has not been pressed yet it will not be in the map.
+    pub buttons: HashMap<Button, (bool, bool)>,
+    /// The current value of each axis, from -1.0 to 1.0. Axes that haven't moved yet are not in
+    /// the map.
+    pub axes: HashMap<Axis, f32>,
+}
+
+impl GamepadState {
+    fn new(gilrs: &Gilrs, id: GamepadId) -> Self {
+        let gamepad = gilrs.gamepad(id);
+        GamepadState {
+            id,
+            name: gamepad.name().to_string(),
+            connected: gamepad.is_connected(),
+            buttons: HashMap::new(),
+            axes: HashMap::new(),
+        }
+    }
+
+    /// If the button was pressed this last frame.
+    pub fn button_pressed(&self, button: Button) -> bool {
+        &(false, true) == self.buttons.get(&button).unwrap_or(&(false, false))
+    }
+
+    /// If the button is currently down.
+    pub fn button_is_down(&self, button: Button) -> bool {
+        matches!(self.buttons.get(&button), Some((_, true)))
+    }
+
+    /// If the button was released this last frame.
+    pub fn button_released(&self, button: Button) -> bool {
+        &(true, false) == self.buttons.get(&button).unwrap_or(&(false, false))
+    }
+
+    /// The current value of the axis, or 0.0 if it hasn't moved yet.
+    pub fn axis(&self, axis: Axis) -> f32 {
+        self.axes.get(&axis).copied().unwrap_or(0.0)
+    }
+}
+
+/// Starts listening for gamepads. If that isn't possible on this platform, the problem is logged
+/// and `BasicInput::gamepads` just stays empty.
+pub(crate) fn init() -> Option<Gilrs> {
+    match Gilrs::new() {
+        Ok(gilrs) => Some(gilrs),
+        Err(err) => {
+            log::warn!("Gamepads are not available: {}", err);
+            None
+        }
+    }
+}
+
+/// Adds the gamepads that were connected before we started listening for events.
+pub(crate) fn add_connected(gilrs: &Gilrs, gamepads: &mut Vec<GamepadState>) {
+    for (id, _) in gilrs.gamepads() {
+        if !gamepads.iter().any(|pad| pad.id == id) {
+            gamepads.push(GamepadState::new(gilrs, id));
+        }
+    }
+}
+
+/// Folds the events that happened since the last call into `gamepads`.
+pub(crate) fn update(gilrs: &mut Gilrs, gamepads: &mut Vec<GamepadState>) {
+    while let Some(event) = gilrs.next_event() {
+        let index = match gamepads.iter().position(|pad| pad.id == event.id) {
+            Some(index) => index,
+            None => {
+                gamepads.push(GamepadState::new(gilrs, event.id));
+                gamepads.len() - 1
+            }
+        };
+        let pad = &mut gamepads[index];
+
+        match event.event {
+            EventType::ButtonPressed(button, _) => {
+                pad.buttons.entry(button).or_insert((false, false)).1 = true;
+            }
+            EventType::ButtonReleased(button, _) => {
+                pad.buttons.entry(button).or_insert((false, false)).1 = false;
+            }
+            EventType::AxisChanged(axis, value, _) => {
+                pad.axes.insert(axis, value);
+            }
+            EventType::Connected => {
+                pad.connected = true;
+            }
+            EventType::Disconnected => {
+                pad.connected = false;
+                // nothing is held down on a gamepad that isn't there
+                pad.buttons.clear();
+                pad.axes.clear();
+            }
+            _ => {}
+        }
+    }
+}
+
+/// Copies the current button states into the previous states, like the loop does for keys.
+pub(crate) fn store_previous(gamepads: &mut [GamepadState]) {
+    for pad in gamepads {
+        for val in pad.buttons.values_mut() {
+            val.0 = val.1;
+        }
+    }
+}
@@ -0,0 +1,104 @@
+//! Reloading shaders automatically when their source files change. Requires the `notify` feature.
+
+use crate::core::Framebuffer;
+use crate::error::MglfbError;
+
+use notify::{DebouncedEvent, RecommendedWatcher, RecursiveMode, Watcher};
+
+use std::path::{Path, PathBuf};
+use std::sync::mpsc::{channel, Receiver};
+use std::time::Duration;
+
+/// How a watched file is turned into a shader.
+#[non_exhaustive]
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum ShaderKind {
+    /// A complete fragment shader. See [`Framebuffer::use_fragment_shader`].
+    Fragment,
+    /// A ShaderToy style `main_image` function. See [`Framebuffer::use_post_process_shader`].
+    PostProcess,
+}
+
+/// Watches a shader source file and recompiles it into a [`Framebuffer`] whenever it changes.
+///
+/// The watcher only notices changes; the shader is recompiled when you call
+/// [`poll`][ShaderWatcher::poll], since that has to happen on the thread the OpenGL context is
+/// current on. Call it once per frame. If the new source doesn't compile, the last shader that did
+/// stays in use and the error is returned so that you can show it.
+///
+/// ```rust,no_run
+/// use mini_gl_fb::hot_reload::{ShaderWatcher, ShaderKind};
+/// use mini_gl_fb::glutin::event::VirtualKeyCode;
+///
+/// let (mut event_loop, mut fb) = mini_gl_fb::gotta_go_fast("Playground", 800.0, 600.0);
+/// let mut watcher = ShaderWatcher::new("shader.glsl", ShaderKind::PostProcess).unwrap();
+///
+/// fb.glutin_handle_basic_input(&mut event_loop, |fb, input| {
+///     match watcher.poll(fb) {
+///         Some(Ok(())) => fb.redraw(),
+///         Some(Err(err)) => eprintln!("{}", err),
+///         None => {}
+///     }
+///     !input.key_is_down(VirtualKeyCode::Escape)
+/// });
+/// ```
+pub struct ShaderWatcher {
+    path: PathBuf,
+    kind: ShaderKind,
+    loaded: bool,
+    events: Receiver<DebouncedEvent>,
+    // dropping the watcher stops it
+    _watcher: RecommendedWatcher,
+}
+
+impl ShaderWatcher {
+    /// Starts watching `path`. The shader is loaded for the first time on the first call to
+    /// [`poll`][ShaderWatcher::poll].
+    pub fn new<P: AsRef<Path>>(path: P, kind: ShaderKind) -> notify::Result<Self> {
+        let path = path.as_ref().canonicalize()?;
+        let (sender, events) = channel();
+        let mut watcher: RecommendedWatcher = Watcher::new(sender, Duration::from_millis(100))?;
+
+        // Many editors save by replacing the file instead of writing to it, which a watch on the
+        // file itself would not survive, so watch the directory and filter by name instead
+        let directory = path.parent().unwrap_or(&path);
+        watcher.watch(directory, RecursiveMode::NonRecursive)?;
+
+        Ok(ShaderWatcher {
+            path,
+            kind,
+            loaded: false,
+            events,
+            _watcher: watcher,
+        })
+    }
+
+    /// The file being watched.
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Reloads the shader into `fb` if the file changed since the last call (or if it has never
+    /// been loaded). Returns `None` if nothing was reloaded, otherwise the result of reloading.
+    pub fn poll(&mut self, fb: &mut Framebuffer) -> Option<Result<(), MglfbError>> {
+        let mut changed = !self.loaded;
+        for event in self.events.try_iter() {
+            match event {
+                DebouncedEvent::Create(path)
+                | DebouncedEvent::Write(path)
+                | DebouncedEvent::Rename(_, path) if path == self.path => changed = true,
+                _ => {}
+            }
+        }
+
+        if !changed {
+            return None;
+        }
+
+        self.loaded = true;
+        Some(match self.kind {
+            ShaderKind::Fragment => fb.use_fragment_shader_from_file(&self.path),
+            ShaderKind::PostProcess => fb.use_post_process_shader_from_file(&self.path),
+        })
+    }
+}
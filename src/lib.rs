@@ -81,7 +81,12 @@
 //!
 //! Currently uses the `gl` crate for OpenGL loading. OpenGL context creation may fail if your
 //! setup does not support the newest OpenGL. This bug needs to be verified and is be fixable.
-//! OpenGL ~3 is currently required, but OpenGL 2.1 support should be feasible if requested.
+//! OpenGL 3.3 is currently required. You can ask for a specific version and profile with
+//! [`Config::gl_version`] and [`Config::gl_profile`].
+//!
+//! [`gotta_go_fast`] and [`get_fancy`] panic if the context can't be created. Use
+//! [`try_get_fancy`] instead if you would rather handle that yourself, for instance to show the
+//! user a friendly message.
 //!
 //! # Feature matrix
 //!
@@ -93,7 +98,7 @@
 //! | `gotta_go_fast`-like function | Yes                   | No                   | No                    |
 //! | Event-based API               | Yes                   | Yes                  | No                    |
 //! | Multi-window                  | Yes                   | Yes                  | Unsupported           |
-//! | Vsync                         | Yes (use glutin)      | Confusing wgpu stuff | Only FPS locking      |
+//! | Vsync                         | Yes                   | Confusing wgpu stuff | Only FPS locking      |
 //! | Buffer allocation             | By user               | Confusing wgpu stuff | By user               |
 //! | Resizable                     | Yes                   | Requires restart     | Yes                   |
 //! | Scalable                      | Yes                   | Integer              | Buggy on Windows      |
@@ -110,18 +115,48 @@ extern crate derive_builder;
 
 pub extern crate glutin;
 pub extern crate gl;
+#[cfg(feature = "image")]
+pub extern crate image;
+#[cfg(feature = "notify")]
+pub extern crate notify;
+#[cfg(feature = "raw-window-handle")]
+pub extern crate raw_window_handle;
+#[cfg(feature = "gilrs")]
+pub extern crate gilrs;
 
 pub mod config;
 pub mod core;
 pub mod breakout;
+pub mod tiled;
+pub mod draw_buffer;
+pub mod error;
+#[cfg(feature = "notify")]
+pub mod hot_reload;
+#[cfg(feature = "draw")]
+pub mod draw;
+#[cfg(feature = "gilrs")]
+pub mod gamepad;
+
+mod vsync;
 
-pub use breakout::{GlutinBreakout, BasicInput};
-pub use config::{Config, ConfigBuilder};
-pub use crate::core::{Internal, BufferFormat, Framebuffer};
+pub use breakout::{GlutinBreakout, BasicInput, DoubleClickThreshold};
+pub use config::{Config, ConfigBuilder, WindowIcon};
+pub use error::{MglfbError, BufferSizeError};
+pub use draw_buffer::DrawBuffer;
+pub use crate::core::{
+    Internal, BufferFormat, Framebuffer, BuiltinShader, Colormap, ColorFormat, UniformValue,
+    ScaleMode, BlendMode, AlphaMode, WrapMode, IntoPixels, Bgr, Bgra, ProgramHandle, ContextInfo
+};
 
-use crate::core::ToGlType;
+use crate::core::{ToGlType, BufferUpload};
 use glutin::event_loop::{EventLoop, EventLoopWindowTarget};
-use glutin::dpi::LogicalSize;
+use glutin::dpi::{LogicalSize, PhysicalPosition};
+use glutin::{Rect, Context, PossiblyCurrent, WindowedContext};
+use glutin::monitor::MonitorHandle;
+use glutin::event::VirtualKeyCode;
+use glutin::window::CursorIcon;
+
+use std::time::Duration;
 
 /// Creates a non-resizable window and framebuffer with a given size in logical pixels. On HiDPI
 /// screens, the physical size of the window may be larger or smaller than the provided values, but
@@ -135,13 +170,37 @@ pub fn gotta_go_fast<S: ToString>(
     window_height: f64
 ) -> (EventLoop<()>, MiniGlFb) {
     let event_loop = EventLoop::new();
+    let fancy = gotta_go_fast_with_loop(window_title, window_width, window_height, &event_loop);
+    (event_loop, fancy)
+}
+
+/// The same as [`gotta_go_fast`], but uses an event loop that you already have instead of creating
+/// a new one.
+///
+/// This is useful if your event loop needs a custom user event type (for instance, so that other
+/// threads can wake it up through an [`EventLoopProxy`][glutin::event_loop::EventLoopProxy]), or if
+/// you are creating multiple windows that all need to share one loop.
+///
+/// ```rust
+/// use mini_gl_fb::glutin::event_loop::EventLoop;
+///
+/// enum MyEvent { FrameReady }
+///
+/// let mut event_loop = EventLoop::<MyEvent>::with_user_event();
+/// let mut fb = mini_gl_fb::gotta_go_fast_with_loop("Hello world!", 800.0, 600.0, &event_loop);
+/// ```
+pub fn gotta_go_fast_with_loop<S: ToString, ET: 'static>(
+    window_title: S,
+    window_width: f64,
+    window_height: f64,
+    event_loop: &EventLoop<ET>
+) -> MiniGlFb {
     let config = config! {
         window_title: window_title.to_string(),
         window_size: LogicalSize::from((window_width, window_height)),
         resizable: false
     };
-    let fancy = get_fancy(config, &event_loop);
-    (event_loop, fancy)
+    get_fancy(config, event_loop)
 }
 
 /// Create a window with a custom configuration.
@@ -153,33 +212,142 @@ pub fn gotta_go_fast<S: ToString>(
 /// `get_fancy` with a custom config. However, if there is a bug in the OS/windowing system or
 /// glutin or in this library, this function exists as a possible work around (or in case for some
 /// reason everything must be absolutely correct at window creation)
+///
+/// # Panics
+///
+/// Panics if the window or OpenGL context can't be created. See [`try_get_fancy`] for a version
+/// that returns the error instead.
 pub fn get_fancy<ET: 'static>(config: Config, event_loop: &EventLoopWindowTarget<ET>) -> MiniGlFb {
+    match try_get_fancy(config, event_loop) {
+        Ok(fb) => fb,
+        Err(err) => panic!("{}", err),
+    }
+}
+
+/// The same as [`get_fancy`], but returns an error instead of panicking if the window or OpenGL
+/// context can't be created.
+///
+/// ```rust
+/// use mini_gl_fb::{try_get_fancy, config};
+/// use mini_gl_fb::glutin::event_loop::EventLoop;
+///
+/// let event_loop = EventLoop::new();
+/// match try_get_fancy(config!(), &event_loop) {
+///     Ok(fb) => { /* ... */ }
+///     Err(err) => eprintln!("Couldn't open a window: {}", err),
+/// }
+/// ```
+pub fn try_get_fancy<ET: 'static>(
+    config: Config,
+    event_loop: &EventLoopWindowTarget<ET>
+) -> Result<MiniGlFb, MglfbError> {
+    fancy(config, event_loop, None)
+}
+
+/// Like [`get_fancy`], but the new window's OpenGL context shares its textures, buffers and shader
+/// programs with the one of `existing`. Object names from one window (for instance the texture in
+/// `existing.internal.fb.internal.texture`) can then be used in the other, instead of uploading
+/// the same data to every window. This saves video memory and setup time when opening many
+/// windows showing the same content.
+///
+/// A few things to keep in mind:
+///
+/// - Only one context is current at a time. The new window's context is current when this
+///   returns, so make `existing`'s context current again (see
+///   [`GlutinBreakout::make_current`]) before drawing to it. The same goes for any pair of shared
+///   windows you switch between.
+/// - Shared objects live as long as any of the sharing contexts does, but deleting one through
+///   either context deletes it for all of them.
+/// - Vertex array objects and framebuffer objects are never shared, so every window still sets up
+///   its own quad.
+///
+/// ```rust
+/// use mini_gl_fb::{get_fancy, get_fancy_shared, config};
+/// use mini_gl_fb::glutin::event_loop::EventLoop;
+///
+/// let event_loop = EventLoop::new();
+/// let first = get_fancy(config! { window_title: "First".into() }, &event_loop);
+/// let second = get_fancy_shared(config! { window_title: "Second".into() }, &event_loop, &first);
+/// ```
+///
+/// # Panics
+///
+/// Panics if the window or OpenGL context can't be created. See [`try_get_fancy_shared`] for a
+/// version that returns the error instead.
+pub fn get_fancy_shared<ET: 'static>(
+    config: Config,
+    event_loop: &EventLoopWindowTarget<ET>,
+    existing: &MiniGlFb
+) -> MiniGlFb {
+    match try_get_fancy_shared(config, event_loop, existing) {
+        Ok(fb) => fb,
+        Err(err) => panic!("{}", err),
+    }
+}
+
+/// The same as [`get_fancy_shared`], but returns an error instead of panicking if the window or
+/// OpenGL context can't be created.
+pub fn try_get_fancy_shared<ET: 'static>(
+    config: Config,
+    event_loop: &EventLoopWindowTarget<ET>,
+    existing: &MiniGlFb
+) -> Result<MiniGlFb, MglfbError> {
+    fancy(config, event_loop, Some(existing.internal.context.context()))
+}
+
+fn fancy<ET: 'static>(
+    config: Config,
+    event_loop: &EventLoopWindowTarget<ET>,
+    shared_with: Option<&Context<PossiblyCurrent>>
+) -> Result<MiniGlFb, MglfbError> {
     let buffer_size = config.buffer_size.unwrap_or_else(|| config.window_size.cast());
 
-    let context = core::init_glutin_context(
-        config.window_title,
-        config.window_size.width,
-        config.window_size.height,
-        config.resizable,
-        event_loop
-    );
+    let context = core::init_glutin_context(&config, shared_with, event_loop)?;
 
     let (vp_width, vp_height) = context.window().inner_size().into();
 
-    let fb = core::init_framebuffer(
+    let mut fb = core::init_framebuffer(
         buffer_size.width,
         buffer_size.height,
         vp_width,
         vp_height,
         config.invert_y
     );
+    fb.scale_mode = config.scale_mode;
+    fb.letterbox_color = config.letterbox_color;
+    fb.background_color = config.background_color;
+    fb.clear_color = config.clear_color;
+    fb.set_srgb(config.srgb);
+    fb.internal.context_id = core::current_context();
 
-    MiniGlFb {
-        internal: Internal {
-            context,
-            fb,
-        }
+    let damage_supported = context.swap_buffers_with_damage_supported();
+
+    let mut internal = Internal {
+        context,
+        fb,
+        damage_supported,
+        aspect_ratio: None,
+        suspended_input: None,
+        quit_key: Some(VirtualKeyCode::Escape),
+        cursor_grab: false,
+        #[cfg(feature = "gilrs")]
+        gilrs: crate::gamepad::init(),
+        close_handler: None,
+        buffer_follows_window: false,
+        window_cursor_position: None,
+    };
+
+    internal.set_cursor_icon(config.cursor_icon);
+    internal.set_buffer_follows_window(config.buffer_follows_window);
+
+    if config.fullscreen {
+        internal.set_fullscreen(true);
     }
+
+    // present something right away, otherwise the first frame is whatever garbage the driver had
+    internal.redraw();
+
+    Ok(MiniGlFb { internal })
 }
 
 /// Main wrapper type.
@@ -197,6 +365,18 @@ pub struct MiniGlFb {
     pub internal: Internal,
 }
 
+/// Gives other libraries access to the native window, for instance to attach a native dialog to
+/// it. Requires the `raw-window-handle` feature.
+///
+/// This uses version 0.3 of `raw-window-handle` to match winit, which has no separate display
+/// handle; on X11 and Wayland the display is part of the window handle.
+#[cfg(feature = "raw-window-handle")]
+unsafe impl raw_window_handle::HasRawWindowHandle for MiniGlFb {
+    fn raw_window_handle(&self) -> raw_window_handle::RawWindowHandle {
+        self.internal.context.window().raw_window_handle()
+    }
+}
+
 impl MiniGlFb {
     /// Updates the backing buffer and draws immediately (swaps buffers).
     ///
@@ -210,10 +390,324 @@ impl MiniGlFb {
         self.internal.update_buffer(image_data);
     }
 
+    /// The same as [`update_buffer`][MiniGlFb::update_buffer], but returns an error with the
+    /// expected and actual sizes instead of panicking if the buffer has the wrong size. Nothing is
+    /// drawn in that case.
+    ///
+    /// ```rust
+    /// # let (_, mut fb) = mini_gl_fb::gotta_go_fast("Hello world!", 2.0, 2.0);
+    /// let buffer = vec![[0u8; 4]; 3];
+    /// if let Err(err) = fb.try_update_buffer(&buffer) {
+    ///     assert_eq!((err.expected, err.actual), (16, 12));
+    /// }
+    /// ```
+    pub fn try_update_buffer<T>(&mut self, image_data: &[T]) -> Result<(), BufferSizeError> {
+        self.internal.try_update_buffer(image_data)
+    }
+
+    /// Streams buffer updates to the GPU through pixel buffer objects instead of uploading them
+    /// synchronously. Worth trying for large buffers that change every frame; see
+    /// [`Framebuffer::enable_pbo_streaming`].
+    pub fn enable_pbo_streaming(&mut self, enable: bool) {
+        self.internal.fb.enable_pbo_streaming(enable);
+    }
+
+    /// Smooths out the buffer when it's shown much smaller than it is, like in a thumbnail, at the
+    /// cost of regenerating mipmaps on every update. See [`Framebuffer::enable_mipmaps`].
+    pub fn enable_mipmaps(&mut self, enable: bool) {
+        self.internal.fb.enable_mipmaps(enable);
+    }
+
+    /// Sets how many pixels apart the rows of the pixel data you upload are, so that part of a
+    /// larger image can be uploaded without copying it into a buffer of its own. `None`, the
+    /// default, means rows are tightly packed. See [`Framebuffer::set_row_length`].
+    pub fn set_row_length(&mut self, row_length: Option<u32>) {
+        self.internal.fb.set_row_length(row_length);
+    }
+
+    /// Logs OpenGL errors (and, if the driver supports `KHR_debug`, warnings with descriptions)
+    /// through the [`log`](https://docs.rs/log) crate, which takes the guesswork out of broken
+    /// shaders and textures. Off by default, although debug builds log errors after every draw
+    /// anyway. Returns `true` if the driver's debug messages could be hooked up; see
+    /// [`Framebuffer::enable_gl_debug`].
+    pub fn enable_gl_debug(&mut self) -> bool {
+        self.internal.fb.enable_gl_debug()
+    }
+
+    /// Updates the backing buffer from pixels whose first row is the top one and draws immediately
+    /// (swaps buffers). Use this for images from the `image` crate, screen grabs and the like,
+    /// which would otherwise show up upside down unless the window was created with
+    /// [`invert_y: false`][Config::invert_y]. With this, it doesn't matter what `invert_y` is.
+    ///
+    /// ```rust
+    /// # let (_, mut fb) = mini_gl_fb::gotta_go_fast("Hello world!", 2.0, 2.0);
+    /// // red on top, blue at the bottom
+    /// let top_down = vec![[255u8, 0, 0, 255], [255, 0, 0, 255], [0, 0, 255, 255], [0, 0, 255, 255]];
+    /// fb.update_buffer_flipped(&top_down);
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if the size of the buffer is wrong, just like
+    /// [`update_buffer`][MiniGlFb::update_buffer].
+    pub fn update_buffer_flipped<T>(&mut self, image_data: &[T]) {
+        self.internal.update_buffer_flipped(image_data);
+    }
+
+    /// Updates a rectangle of the backing buffer and draws immediately (swaps buffers).
+    ///
+    /// Only the `width * height` pixels in `data` are uploaded, which is much faster than
+    /// [`update_buffer`][MiniGlFb::update_buffer] if only a small part of the image changed. `(x, y)`
+    /// is the position of the first pixel of `data` in the buffer, using the same coordinates and
+    /// row order as the buffer itself. See [`Framebuffer::update_buffer_region`].
+    ///
+    /// ```rust
+    /// # use mini_gl_fb::get_fancy;
+    /// # use mini_gl_fb::glutin::event_loop::EventLoop;
+    /// # let mut fb = get_fancy(Default::default(), &EventLoop::new());
+    /// # let (width, height) = (600, 480);
+    /// fb.update_buffer(&vec![[0u8, 0, 0, 255]; width * height]);
+    /// // draw a 2x2 white square 10 pixels from the corner
+    /// fb.update_buffer_region(&[[255u8, 255, 255, 255]; 4], 10, 10, 2, 2);
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if the rectangle does not fit inside the buffer, or if the size of `data` does not
+    /// match `width` and `height`.
+    pub fn update_buffer_region<T>(&mut self, data: &[T], x: u32, y: u32, width: u32, height: u32) {
+        self.internal.update_buffer_region(data, x, y, width, height);
+    }
+
+    /// Updates the backing buffer and draws immediately, picking the buffer format from the type of
+    /// pixel you pass in.
+    ///
+    /// `[u8; 4]` pixels are shown as RGBA, `[u8; 3]` as RGB, and single `u8`s are shown in
+    /// grayscale. `u16` and `f32` components work too, and [`Bgra`] and [`Bgr`] wrap pixels with
+    /// their colors the other way around. See [`IntoPixels`] for the full list. Because the format
+    /// follows from the type, a buffer can't be uploaded with the wrong number of components like
+    /// it can with `change_buffer_format` and `update_buffer`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use mini_gl_fb::{get_fancy, config};
+    /// # use mini_gl_fb::glutin::event_loop::EventLoop;
+    /// # use mini_gl_fb::glutin::dpi::LogicalSize;
+    /// # let mut fb = get_fancy(config! { buffer_size: Some(LogicalSize::new(2, 2)) }, &EventLoop::new());
+    /// // Four shades of gray
+    /// fb.show(&[128u8, 255, 50, 25]);
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if the number of pixels does not match the size of the buffer.
+    pub fn show<P: IntoPixels>(&mut self, pixels: &[P]) {
+        self.internal.show(pixels);
+    }
+
+    /// The same as [`show`][MiniGlFb::show], but returns an error with the expected and actual
+    /// sizes in bytes instead of panicking if the number of pixels doesn't match the size of the
+    /// buffer. Nothing is drawn in that case.
+    ///
+    /// ```rust
+    /// # let (_, mut fb) = mini_gl_fb::gotta_go_fast("Hello world!", 2.0, 2.0);
+    /// use mini_gl_fb::Bgra;
+    ///
+    /// let buffer = vec![Bgra([255u8, 0, 0, 255]); 3];
+    /// if let Err(err) = fb.try_show(&buffer) {
+    ///     assert_eq!((err.expected, err.actual), (16, 12));
+    /// }
+    /// ```
+    pub fn try_show<P: IntoPixels>(&mut self, pixels: &[P]) -> Result<(), BufferSizeError> {
+        self.internal.try_show(pixels)
+    }
+
+    /// Uploads what changed in a [`DrawBuffer`] since it was last flushed, and draws immediately
+    /// (swaps buffers) if anything did. Returns whether anything was uploaded. See
+    /// [`DrawBuffer::flush`].
+    ///
+    /// ```rust
+    /// # let (_, mut fb) = mini_gl_fb::gotta_go_fast("Hello world!", 4.0, 4.0);
+    /// use mini_gl_fb::DrawBuffer;
+    ///
+    /// let mut buffer = DrawBuffer::new(4, 4, [0u8, 0, 0, 255]);
+    /// assert!(fb.flush_draw_buffer(&mut buffer));
+    ///
+    /// // only this pixel is uploaded
+    /// buffer.set_pixel(1, 2, [255, 0, 0, 255]);
+    /// assert!(fb.flush_draw_buffer(&mut buffer));
+    /// assert!(!fb.flush_draw_buffer(&mut buffer));
+    /// ```
+    pub fn flush_draw_buffer<P: IntoPixels + Copy>(&mut self, buffer: &mut DrawBuffer<P>) -> bool {
+        self.internal.flush_draw_buffer(buffer)
+    }
+
+    /// Uploads a buffer that was prepared on another thread, and draws immediately (swaps
+    /// buffers).
+    ///
+    /// The buffer format and size are changed to match the upload. The pixel data is returned so
+    /// that its allocation can be reused. See [`BufferUpload`] for more information.
+    pub fn upload<T: ToGlType>(&mut self, upload: BufferUpload<T>) -> Vec<T> {
+        self.internal.upload(upload)
+    }
+
+    /// Draws the current buffer again and swaps buffers, for instance after changing a shader
+    /// uniform.
+    ///
+    /// Until a buffer has been uploaded there is nothing to draw, so this fills the window with
+    /// [`Config::background_color`] instead, without running any shaders. Custom shaders may not
+    /// need a buffer, so once one is in use (set with a `use_*_shader` method or
+    /// [`Framebuffer::use_program`]) the buffer is always drawn.
     pub fn redraw(&mut self) {
         self.internal.redraw();
     }
 
+    /// Changes how the buffer is fitted into the window and redraws. Use [`ScaleMode::AspectFit`]
+    /// to keep the buffer's aspect ratio with borders around it, instead of stretching it to fill
+    /// the window. The initial mode comes from [`Config::scale_mode`].
+    ///
+    /// The mouse position reported by
+    /// [`glutin_handle_basic_input`][MiniGlFb::glutin_handle_basic_input] takes the borders into
+    /// account, so it is still in buffer coordinates (and out of range over the borders).
+    pub fn set_scale_mode(&mut self, scale_mode: ScaleMode) {
+        self.internal.fb.set_scale_mode(scale_mode);
+        self.internal.redraw();
+    }
+
+    /// Changes the RGBA color (components from 0 to 1) of the borders shown in
+    /// [`ScaleMode::AspectFit`] and redraws.
+    pub fn set_letterbox_color(&mut self, color: [f32; 4]) {
+        self.internal.fb.set_letterbox_color(color);
+        self.internal.redraw();
+    }
+
+    /// Fills the window with a solid color and shows it immediately (swaps buffers), without
+    /// uploading anything. The color is RGBA with components from 0 to 1.
+    ///
+    /// The buffer itself is left alone, so the next [`redraw`][MiniGlFb::redraw] shows it again.
+    /// To clear the buffer as well, use [`update_buffer`][MiniGlFb::update_buffer].
+    ///
+    /// ```rust
+    /// # use mini_gl_fb::get_fancy;
+    /// # use mini_gl_fb::glutin::event_loop::EventLoop;
+    /// # let mut fb = get_fancy(Default::default(), &EventLoop::new());
+    /// fb.clear([0.1, 0.1, 0.1, 1.0]);
+    /// ```
+    pub fn clear(&mut self, color: [f32; 4]) {
+        self.internal.clear(color);
+    }
+
+    /// Returns how many bits per color component the window actually got, and whether it is sRGB.
+    ///
+    /// See [`ColorFormat`] for more information.
+    pub fn color_format(&self) -> ColorFormat {
+        self.internal.fb.color_format()
+    }
+
+    /// Returns the OpenGL version, renderer and vendor of the context, along with the number of
+    /// samples and the format the window's color buffer actually got.
+    ///
+    /// See [`ContextInfo`] for more information.
+    ///
+    /// ```rust
+    /// # let (_, fb) = mini_gl_fb::gotta_go_fast("Hello world!", 800.0, 600.0);
+    /// let info = fb.context_info();
+    /// println!("Running on {} with OpenGL {}", info.renderer, info.version);
+    /// ```
+    pub fn context_info(&self) -> ContextInfo {
+        self.internal.fb.context_info()
+    }
+
+    /// Returns the current contents of the window as tightly packed 8-bit RGBA pixels, for saving
+    /// screenshots or checking the output in tests.
+    ///
+    /// The image is the size of the window in physical pixels, not the size of the buffer. Rows
+    /// are ordered the same way as in the buffer, so with the default
+    /// [`invert_y`][Config::invert_y] the first row is the bottom of the window. With
+    /// `unpremultiply`, colors are converted from premultiplied to straight alpha, which matters
+    /// for [transparent][Config::transparent] windows. See [`Framebuffer::read_pixels`] for
+    /// details.
+    ///
+    /// ```rust
+    /// # use mini_gl_fb::get_fancy;
+    /// # use mini_gl_fb::glutin::event_loop::EventLoop;
+    /// # let mut fb = get_fancy(Default::default(), &EventLoop::new());
+    /// let pixels = fb.screenshot(false);
+    /// let size = fb.internal.fb.vp_size;
+    /// assert_eq!(pixels.len(), size.width as usize * size.height as usize * 4);
+    /// ```
+    pub fn screenshot(&mut self, unpremultiply: bool) -> Vec<u8> {
+        // the back buffer is undefined after the last swap, so draw it again first
+        self.internal.fb.redraw();
+        self.internal.fb.read_pixels(unpremultiply)
+    }
+
+    /// Saves the current contents of the window to a PNG file. Requires the `image` feature.
+    ///
+    /// The image is saved right side up regardless of [`invert_y`][Config::invert_y], at the size
+    /// of the window in physical pixels. Alpha is saved as the window has it: opaque windows have
+    /// no alpha channel and come out fully opaque, while [transparent][Config::transparent]
+    /// windows keep their transparency. Since PNG expects straight alpha, pass `true` for
+    /// `unpremultiply` if the window holds premultiplied colors, as transparent windows usually
+    /// do. Use [`screenshot`][MiniGlFb::screenshot] if you need the raw pixels instead.
+    ///
+    /// ```rust,no_run
+    /// # use mini_gl_fb::get_fancy;
+    /// # use mini_gl_fb::glutin::event_loop::EventLoop;
+    /// # let mut fb = get_fancy(Default::default(), &EventLoop::new());
+    /// fb.save_screenshot("screenshot.png", false).expect("couldn't save screenshot");
+    /// ```
+    #[cfg(feature = "image")]
+    pub fn save_screenshot<P: AsRef<std::path::Path>>(
+        &mut self, path: P, unpremultiply: bool
+    ) -> image::ImageResult<()> {
+        let size = self.internal.fb.vp_size;
+        let mut pixels = self.screenshot(unpremultiply);
+
+        // images are stored top row first
+        if self.internal.fb.inverted_y {
+            core::flip_rows(&mut pixels, size.width as usize * 4);
+        }
+
+        image::save_buffer_with_format(
+            path,
+            &pixels,
+            size.width as u32,
+            size.height as u32,
+            image::ColorType::Rgba8,
+            image::ImageFormat::Png,
+        )
+    }
+
+    /// Blocks until the GPU has finished all the work it has been given. See
+    /// [`Framebuffer::finish`] for when (not) to use this.
+    pub fn finish(&self) {
+        self.internal.fb.finish();
+    }
+
+    /// Makes the GPU start on all the work it has been given, without waiting for it to finish.
+    pub fn flush(&self) {
+        self.internal.fb.flush();
+    }
+
+    /// Presents the frame, telling the windowing system that only the given regions of the window
+    /// have changed since the last frame.
+    ///
+    /// On platforms that support it (`EGL_KHR_swap_buffers_with_damage`, and similar extensions),
+    /// this allows the compositor to skip copying the parts of the window that stayed the same,
+    /// which saves a lot of bandwidth for mostly static content like terminals or UIs. Everywhere
+    /// else this falls back to a normal buffer swap, so it is always safe to call. Whether the
+    /// extension is available can be checked with [`Internal::damage_supported`].
+    ///
+    /// Rectangles are in physical pixels of the window, with the origin at the bottom left.
+    ///
+    /// Unlike most `MiniGlFb` methods this does not draw anything, it only swaps buffers. Draw
+    /// first using the [`Framebuffer`] methods, which do not swap buffers themselves.
+    pub fn swap_buffers_with_damage(&mut self, rects: &[Rect]) {
+        self.internal.swap_buffers_with_damage(rects);
+    }
+
     /// Use a custom post process shader written in GLSL (version 330 core).
     ///
     /// The interface is unapologetically similar to ShaderToy's. It works by inserting your code
@@ -232,23 +726,135 @@ impl MiniGlFb {
     ///     void main_image( out vec4 r_frag_color, in vec2 v_uv ) {
     ///         r_frag_color = texture(u_buffer, v_uv);
     ///     }
-    /// ");
+    /// ").unwrap();
+    /// ```
+    ///
+    /// If your shader doesn't compile, the error is returned with the compiler's info log and the
+    /// previous shader is kept, so you can show the error and let the user try again:
+    ///
+    /// ```rust
+    /// # use mini_gl_fb::get_fancy;
+    /// # use mini_gl_fb::glutin::event_loop::EventLoop;
+    /// # let mut fb = get_fancy(Default::default(), &EventLoop::new());
+    /// if let Err(err) = fb.use_post_process_shader("this is not glsl") {
+    ///     eprintln!("{}", err);
+    /// }
     /// ```
     ///
-    /// Regardless of the format of your buffer, the internal texture is always stored as RGBA,
-    /// so sampling u_buffer will yield a vec4 representing an RGBA color. The built in grayscale
-    /// shader, for instance, only stores Red components, and then uses the red component for the
-    /// green and blue components to create gray.
+    /// Regardless of the format of your buffer, the internal texture always has four channels, so
+    /// sampling u_buffer will yield a vec4 representing an RGBA color. How precise the channels are
+    /// depends on the component type (see [`change_buffer_format`][MiniGlFb::change_buffer_format]);
+    /// `f32` buffers in particular aren't clamped to 0 to 1. The built in grayscale shader, for
+    /// instance, only stores Red components, and then uses the red component for the green and blue
+    /// components to create gray.
     ///
     /// The output color is determined by the value of the first output parameter, `r_frag_color`.
-    /// Your buffer is accessible as a 2D sampler uniform named `u_buffer`. The first input
+    /// Your buffer is accessible as a 2D sampler uniform named `u_buffer` (see
+    /// [`BUFFER_SAMPLER_NAME`][core::BUFFER_SAMPLER_NAME]). The first input
     /// parameter `v_uv` is a vec2 UV coordinate. UV (0, 0) represents the bottom left of the
     /// screen and (1, 1) represents the top right.
     ///
-    /// An API for exposing more built in and custom uniforms is planned, along with support for
-    /// an arbitrary number of render targets and possibly more user supplied textures.
-    pub fn use_post_process_shader(&mut self, source: &str) {
-        self.internal.fb.use_post_process_shader(source);
+    /// Like on ShaderToy, a few more uniforms are set every time the buffer is drawn:
+    ///
+    ///  - `vec2 u_resolution`: the size of the viewport in physical pixels.
+    ///  - `float u_time`: seconds since the framebuffer was created.
+    ///  - `vec2 u_mouse`: the last known mouse position in physical pixels, with (0, 0) at the
+    ///    bottom left of the viewport. See [`Framebuffer::mouse_position`].
+    ///
+    /// Shaders are only redrawn when you draw, so animate `u_time` by calling
+    /// [`redraw`][MiniGlFb::redraw] every frame.
+    ///
+    /// With [`Config::srgb`] enabled, sampling `u_buffer` gives linear colors rather than the
+    /// sRGB values in your buffer, and `r_frag_color` is converted back to sRGB when it's written,
+    /// so do your color math in linear space and don't apply any gamma yourself.
+    ///
+    /// You can pass your own values in as well, with [`set_uniform_f32`][MiniGlFb::set_uniform_f32]
+    /// and friends. Support for an arbitrary number of render targets and possibly more user
+    /// supplied textures is planned.
+    pub fn use_post_process_shader(&mut self, source: &str) -> Result<(), MglfbError> {
+        self.internal.fb.use_post_process_shader(source)
+    }
+
+    /// Replaces the vertex shader with a complete GLSL shader of your own.
+    ///
+    /// The quad is drawn as two triangles with a `vec2` position (in normalized device
+    /// coordinates) at attribute location 0 and a `vec2` texture coordinate at location 1. The
+    /// default vertex shader passes the texture coordinate on as `out vec2 v_uv`, which is what the
+    /// default and post-process fragment shaders expect.
+    ///
+    /// If the shader doesn't compile or link, the error is returned and the previous shader is
+    /// kept. See the `custom_shaders` example.
+    pub fn use_vertex_shader(&mut self, source: &str) -> Result<(), MglfbError> {
+        self.internal.fb.use_vertex_shader(source)
+    }
+
+    /// Adds a geometry shader, which runs between the vertex and fragment shaders and can turn the
+    /// two triangles of the quad into any geometry you like. The `custom_shaders` example turns
+    /// them into a circle.
+    ///
+    /// If the shader doesn't compile or link, the error is returned and the previous shader is
+    /// kept.
+    pub fn use_geometry_shader(&mut self, source: &str) -> Result<(), MglfbError> {
+        self.internal.fb.use_geometry_shader(source)
+    }
+
+    /// Replaces the fragment shader with a complete GLSL shader of your own. Unlike
+    /// [`use_post_process_shader`][MiniGlFb::use_post_process_shader], nothing is added to the
+    /// source, so it has to declare its own inputs, outputs and uniforms, including
+    /// `uniform sampler2D u_buffer;` for the buffer.
+    ///
+    /// If the shader doesn't compile or link, the error is returned and the previous shader is
+    /// kept.
+    pub fn use_fragment_shader(&mut self, source: &str) -> Result<(), MglfbError> {
+        self.internal.fb.use_fragment_shader(source)
+    }
+
+    /// Compiles a complete program up front, so that you can switch to it with
+    /// [`use_program`][MiniGlFb::use_program] as often as you like without recompiling. `vertex`
+    /// defaults to the built-in vertex shader. See [`Framebuffer::register_program`].
+    ///
+    /// ```rust
+    /// # use mini_gl_fb::get_fancy;
+    /// # use mini_gl_fb::glutin::event_loop::EventLoop;
+    /// # let mut fb = get_fancy(Default::default(), &EventLoop::new());
+    /// let negative = fb.register_program("negative", None, r"
+    ///     #version 330 core
+    ///     in vec2 v_uv;
+    ///     out vec4 r_frag_color;
+    ///     uniform sampler2D u_buffer;
+    ///     void main() {
+    ///         vec4 color = texture(u_buffer, v_uv);
+    ///         r_frag_color = vec4(1.0 - color.rgb, color.a);
+    ///     }
+    /// ", None).unwrap();
+    ///
+    /// // switch back and forth as often as you like
+    /// fb.use_program(negative);
+    /// fb.redraw();
+    /// fb.use_linked_program();
+    /// fb.redraw();
+    /// ```
+    pub fn register_program(
+        &mut self,
+        name: &str,
+        vertex: Option<&str>,
+        fragment: &str,
+        geometry: Option<&str>,
+    ) -> Result<ProgramHandle, MglfbError> {
+        self.internal.fb.register_program(name, vertex, fragment, geometry)
+    }
+
+    /// Switches to a program registered with [`register_program`][MiniGlFb::register_program],
+    /// which is cheap. Use [`use_linked_program`][MiniGlFb::use_linked_program] to go back to the
+    /// shaders set with the `use_*_shader` methods.
+    pub fn use_program(&mut self, handle: ProgramHandle) {
+        self.internal.fb.use_program(handle);
+    }
+
+    /// Switches back to the shaders set with the `use_*_shader` methods after
+    /// [`use_program`][MiniGlFb::use_program].
+    pub fn use_linked_program(&mut self) {
+        self.internal.fb.use_linked_program();
     }
 
     /// Changes the format of the image buffer.
@@ -262,11 +868,12 @@ impl MiniGlFb {
     /// the buffer format to BufferFormat::R, and call `use_grayscale_shader` (which will replace
     /// the fragment shader with one that sets all components equal to the red component).
     ///
-    /// The type `T` does not affect how the texture is sampled, only how the buffer you pass is
-    /// interpreted. Since there is no way exposed to change the internal format of the texture,
-    /// (for instance if you wanted to make it an HDR image with floating point components) only
-    /// the types `u8` and `i8` are supported. Open an issue if you have a use case for other
-    /// types.
+    /// The type `T` is the type of each component in the buffer you pass. `u8`, `i8`, `u16`,
+    /// `i16`, `u32`, `i32` and `f32` are supported. The texture is stored with enough precision for
+    /// the type, so 16-bit data (depth maps, heightmaps) keeps all of its bits. Integer components
+    /// are normalized to the range 0 to 1 when they reach the shader, as usual. Floating point
+    /// components are not clamped, so an HDR buffer can be tone mapped in a post process shader
+    /// (the window itself can of course only show 0 to 1).
     ///
     /// # Example
     ///
@@ -279,6 +886,28 @@ impl MiniGlFb {
     /// fb.change_buffer_format::<u8>(BufferFormat::R);
     /// fb.use_grayscale_shader();
     /// ```
+    ///
+    /// Uploading floating point data, here to an offscreen framebuffer so the result can be
+    /// checked. Values above 1 reach the shader intact, so halving them in a post process shader
+    /// gives full intensity rather than the half it would be if they had been clamped:
+    ///
+    /// ```rust
+    /// use mini_gl_fb::BufferFormat;
+    /// use mini_gl_fb::core::init_headless_framebuffer;
+    /// use mini_gl_fb::glutin::event_loop::EventLoop;
+    ///
+    /// let event_loop = EventLoop::new();
+    /// let mut headless = init_headless_framebuffer(4, 4, &event_loop).unwrap();
+    /// let fb = &mut headless.fb;
+    /// fb.change_buffer_format::<f32>(BufferFormat::RGBA);
+    /// fb.use_post_process_shader("
+    ///     void main_image(out vec4 r_frag_color, in vec2 v_uv) {
+    ///         r_frag_color = texture(u_buffer, v_uv) / 2.0;
+    ///     }
+    /// ").unwrap();
+    /// fb.update_buffer(&[[2.0f32, 0.0, 2.0, 2.0]; 4 * 4]);
+    /// assert_eq!(&fb.read_pixels(false)[0..4], &[255, 0, 255, 255]);
+    /// ```
     pub fn change_buffer_format<T: ToGlType>(&mut self, format: BufferFormat) {
         self.internal.fb.change_buffer_format::<T>(format);
     }
@@ -290,14 +919,180 @@ impl MiniGlFb {
         self.internal.fb.resize_buffer(buffer_width, buffer_height);
     }
 
+    /// Resizes the buffer while keeping its current contents, filling any new space with `fill`,
+    /// which is one pixel in the current buffer format. Does not redraw.
+    ///
+    /// See [`Framebuffer::resize_buffer_preserving`] for the details.
+    ///
+    /// ```rust
+    /// # let (_, mut fb) = mini_gl_fb::gotta_go_fast("Hello world!", 800.0, 600.0);
+    /// fb.update_buffer(&vec![[255u8, 0, 0, 255]; 800 * 600]);
+    /// // the old 800x600 stays red, the rest of the new buffer is black
+    /// fb.resize_buffer_preserving(1024, 768, &[0u8, 0, 0, 255]);
+    /// fb.redraw();
+    /// ```
+    pub fn resize_buffer_preserving<T>(&mut self, buffer_width: u32, buffer_height: u32, fill: &[T]) {
+        self.internal.fb.resize_buffer_preserving(buffer_width, buffer_height, fill);
+    }
+
+    /// Returns the size of the buffer in pixels, as (width, height).
+    pub fn buffer_size(&self) -> (u32, u32) {
+        self.internal.fb.buffer_size()
+    }
+
+    /// Returns the size of the inside of the window (without decorations) in physical pixels, as
+    /// (width, height). This is the size the buffer is stretched to.
+    pub fn physical_size(&self) -> (u32, u32) {
+        self.internal.physical_size()
+    }
+
+    /// Returns the size of the inside of the window in logical pixels, as (width, height). This is
+    /// the physical size divided by the [`scale_factor`][MiniGlFb::scale_factor], and what
+    /// [`Config::window_size`] is measured in.
+    ///
+    /// For a buffer with one pixel per logical pixel, resize it to this (rounded down) when the
+    /// window is resized:
+    ///
+    /// ```rust
+    /// # let (_, mut fb) = mini_gl_fb::gotta_go_fast("Hello world!", 800.0, 600.0);
+    /// let (width, height) = fb.logical_size();
+    /// fb.resize_buffer(width as u32, height as u32);
+    /// ```
+    pub fn logical_size(&self) -> (f64, f64) {
+        self.internal.logical_size()
+    }
+
+    /// Returns the number of physical pixels per logical pixel of the monitor the window is on,
+    /// for instance 2.0 on most HiDPI displays. It changes when the window is moved to a monitor
+    /// with a different DPI.
+    pub fn scale_factor(&self) -> f64 {
+        self.internal.scale_factor()
+    }
+
+    /// Returns the number of components (not bytes) a buffer passed to
+    /// [`update_buffer`][MiniGlFb::update_buffer] needs, which is the number of pixels times the
+    /// number of components in the current [`BufferFormat`]. Handy for allocating one:
+    ///
+    /// ```rust
+    /// # let (_, mut fb) = mini_gl_fb::gotta_go_fast("Hello world!", 800.0, 600.0);
+    /// let buffer = vec![128u8; fb.buffer_len()];
+    /// fb.update_buffer(&buffer);
+    /// ```
+    pub fn buffer_len(&self) -> usize {
+        self.internal.fb.buffer_len()
+    }
+
     /// Switch to a shader that only uses the first component from your buffer.
     ///
     /// This **does not** switch to a shader which converts RGB(A) images to grayscale, for
-    /// instance, by preserving percieved luminance.
+    /// instance, by preserving percieved luminance. For that, see
+    /// [`use_luminance_shader`][MiniGlFb::use_luminance_shader].
     pub fn use_grayscale_shader(&mut self) {
         self.internal.fb.use_grayscale_shader();
     }
 
+    /// Switch to a shader that shows a color buffer in grayscale, preserving perceived brightness.
+    /// Each pixel becomes `dot(rgb, vec3(0.2126, 0.7152, 0.0722))` (the Rec. 709 luma
+    /// coefficients) in all three channels, and its alpha is kept.
+    ///
+    /// This is what you want to "make it grayscale" when you already have RGB(A) data. For buffers
+    /// with a single component, use [`use_grayscale_shader`][MiniGlFb::use_grayscale_shader]
+    /// instead.
+    pub fn use_luminance_shader(&mut self) {
+        self.internal.fb.use_luminance_shader();
+    }
+
+    /// Switch to indexed color, like on old 8-bit hardware: from now on, the buffer has one `u8`
+    /// per pixel, which is an index into `palette`, a table of 256 RGBA colors.
+    ///
+    /// ```rust
+    /// # let (_, mut fb) = mini_gl_fb::gotta_go_fast("Hello world!", 800.0, 600.0);
+    /// let mut palette = [[0u8, 0, 0, 255]; 256];
+    /// for (i, color) in palette.iter_mut().enumerate() {
+    ///     color[0] = i as u8;
+    ///     color[2] = 255 - i as u8;
+    /// }
+    /// fb.use_palette(&palette);
+    ///
+    /// let indices: Vec<u8> = (0..800 * 600).map(|i| (i % 800 * 256 / 800) as u8).collect();
+    /// fb.update_buffer(&indices);
+    /// ```
+    ///
+    /// To change the colors later without touching the buffer, use
+    /// [`set_palette`][MiniGlFb::set_palette]. See [`Framebuffer::use_palette`] for details.
+    pub fn use_palette(&mut self, palette: &[[u8; 4]; 256]) {
+        self.internal.fb.use_palette(palette);
+    }
+
+    /// Changes the colors of the palette set with [`use_palette`][MiniGlFb::use_palette], without
+    /// compiling anything or touching the buffer, so it's cheap enough for palette cycling every
+    /// frame. Call [`redraw`][MiniGlFb::redraw] afterwards to see the new colors.
+    pub fn set_palette(&mut self, palette: &[[u8; 4]; 256]) {
+        self.internal.fb.set_palette(palette);
+    }
+
+    /// Switch to one of the built in fragment shaders.
+    ///
+    /// ```rust
+    /// use mini_gl_fb::{BuiltinShader, Colormap};
+    /// # use mini_gl_fb::get_fancy;
+    /// # use mini_gl_fb::glutin::event_loop::EventLoop;
+    /// # let mut fb = get_fancy(Default::default(), &EventLoop::new());
+    ///
+    /// fb.use_builtin_shader(BuiltinShader::Colormap(Colormap::Viridis));
+    /// ```
+    ///
+    /// See [`BuiltinShader`] for the available shaders.
+    pub fn use_builtin_shader(&mut self, shader: BuiltinShader) {
+        self.internal.fb.use_builtin_shader(shader);
+    }
+
+    /// Sets a `float` uniform in your shaders, for instance to pass in a brightness setting.
+    ///
+    /// The value is remembered and applied every time the buffer is drawn, so it only needs to be
+    /// set again when it changes. It takes effect on the next redraw. Uniforms that your shaders
+    /// don't use are ignored.
+    ///
+    /// ```rust
+    /// # use mini_gl_fb::get_fancy;
+    /// # use mini_gl_fb::glutin::event_loop::EventLoop;
+    /// # let mut fb = get_fancy(Default::default(), &EventLoop::new());
+    /// fb.use_post_process_shader("
+    ///     uniform float u_brightness;
+    ///
+    ///     void main_image( out vec4 r_frag_color, in vec2 v_uv ) {
+    ///         r_frag_color = texture(u_buffer, v_uv) * u_brightness;
+    ///     }
+    /// ").unwrap();
+    /// fb.set_uniform_f32("u_brightness", 0.5);
+    /// fb.redraw();
+    /// ```
+    pub fn set_uniform_f32(&mut self, name: &str, value: f32) {
+        self.internal.fb.set_uniform_f32(name, value);
+    }
+
+    /// Sets a `vec2` uniform in your shaders. See [`set_uniform_f32`][MiniGlFb::set_uniform_f32].
+    pub fn set_uniform_vec2(&mut self, name: &str, value: [f32; 2]) {
+        self.internal.fb.set_uniform_vec2(name, value);
+    }
+
+    /// Sets a `vec4` uniform in your shaders. See [`set_uniform_f32`][MiniGlFb::set_uniform_f32].
+    pub fn set_uniform_vec4(&mut self, name: &str, value: [f32; 4]) {
+        self.internal.fb.set_uniform_vec4(name, value);
+    }
+
+    /// Sets an `int` uniform in your shaders. See [`set_uniform_f32`][MiniGlFb::set_uniform_f32].
+    pub fn set_uniform_i32(&mut self, name: &str, value: i32) {
+        self.internal.fb.set_uniform_i32(name, value);
+    }
+
+    /// Sets a uniform whose type is only known at runtime, for instance one loaded from a settings
+    /// file, as a [`UniformValue`]. The typed `set_uniform_*` methods are shorthands for this. See
+    /// [`set_uniform_f32`][MiniGlFb::set_uniform_f32].
+    pub fn set_uniform(&mut self, name: &str, value: UniformValue) {
+        self.internal.fb.set_uniform(name, value);
+    }
+
     /// Set the size of the OpenGL viewport (does not trigger a redraw).
     ///
     /// For high DPI screens this is the physical size of the viewport.
@@ -308,6 +1103,9 @@ impl MiniGlFb {
     ///
     /// You will know if you need to call this function, as in that case only part of the window
     /// will be getting drawn, typically after an update.
+    ///
+    /// A width or height of 0, which some platforms report while the window is minimized, is
+    /// ignored and the previous size is kept.
     pub fn resize_viewport(&mut self, width: u32, height: u32) {
         self.internal.fb.resize_viewport(width, height);
     }
@@ -321,14 +1119,410 @@ impl MiniGlFb {
         self.internal.set_resizable(resizable);
     }
 
+    /// Shows or hides the window. A hidden window doesn't show up in the taskbar either, but keeps
+    /// running; show it again with `set_visible(true)`.
+    pub fn set_visible(&mut self, visible: bool) {
+        self.internal.set_visible(visible);
+    }
+
+    /// Minimizes the window, or restores it from being minimized. While it is minimized, you can
+    /// throttle rendering with [`BasicInput::minimized`] and [`BasicInput::wait_when_minimized`].
+    pub fn set_minimized(&mut self, minimized: bool) {
+        self.internal.set_minimized(minimized);
+    }
+
+    /// Changes the title of the window. The initial title comes from [`Config::window_title`].
+    ///
+    /// This is cheap enough to call every frame, for instance to show the framerate.
+    pub fn set_title(&mut self, title: &str) {
+        self.internal.set_title(title);
+    }
+
+    /// Changes what the cursor looks like while it's over the window. The initial icon comes from
+    /// [`Config::cursor_icon`].
+    pub fn set_cursor_icon(&mut self, icon: CursorIcon) {
+        self.internal.set_cursor_icon(icon);
+    }
+
+    /// Shows or hides the cursor while it's over the window.
+    pub fn set_cursor_visible(&mut self, visible: bool) {
+        self.internal.set_cursor_visible(visible);
+    }
+
+    /// Grabs the cursor, so that it can't leave the window, or lets go of it again. Together with
+    /// [`set_cursor_visible`][MiniGlFb::set_cursor_visible] this gives you first-person style
+    /// mouse controls.
+    ///
+    /// Some platforms let go of the cursor when the window loses focus; the input and `persist`
+    /// loops grab it again when the window regains focus. Returns [`MglfbError::Cursor`] if the
+    /// platform doesn't support grabbing (for instance Wayland without the right protocols) or
+    /// refuses for now (for instance X11 while the window isn't focused).
+    pub fn set_cursor_grab(&mut self, grab: bool) -> Result<(), MglfbError> {
+        self.internal.set_cursor_grab(grab)
+    }
+
+    /// Returns where the cursor was last seen over the window, in buffer coordinates like
+    /// [`BasicInput::mouse_pos`], or `None` if it isn't over the window (or hasn't moved since the
+    /// window was created).
+    ///
+    /// This is kept up to date by `persist` and `glutin_handle_basic_input`, from the same events
+    /// they already handle, so it's only as recent as the last time one of them ran. Inside a basic
+    /// input handler it is the same as `input.mouse_pos`.
+    pub fn cursor_position(&self) -> Option<(f64, f64)> {
+        self.internal.cursor_position()
+    }
+
+    /// Moves the cursor to a position in buffer coordinates, like the ones
+    /// [`cursor_position`][MiniGlFb::cursor_position] returns. Moving it back to the middle of the
+    /// window every frame while it is [grabbed][MiniGlFb::set_cursor_grab] and hidden keeps it
+    /// from ever reaching the edge, for camera controls.
+    ///
+    /// Returns [`MglfbError::Cursor`] if the platform doesn't allow it; Wayland, for one, doesn't
+    /// let applications move the cursor.
+    ///
+    /// ```rust
+    /// # let (_, mut fb) = mini_gl_fb::gotta_go_fast("Hello world!", 800.0, 600.0);
+    /// let (width, height) = fb.buffer_size();
+    /// let _ = fb.set_cursor_position(width as f64 / 2.0, height as f64 / 2.0);
+    /// ```
+    pub fn set_cursor_position(&mut self, x: f64, y: f64) -> Result<(), MglfbError> {
+        self.internal.set_cursor_position(x, y)
+    }
+
+    /// Shows or hides the window's titlebar and borders. The initial setting comes from
+    /// [`Config::decorations`].
+    pub fn set_decorations(&mut self, decorations: bool) {
+        self.internal.set_decorations(decorations);
+    }
+
+    /// Sets whether the window stays on top of other windows. The initial setting comes from
+    /// [`Config::always_on_top`].
+    pub fn set_always_on_top(&mut self, always_on_top: bool) {
+        self.internal.set_always_on_top(always_on_top);
+    }
+
+    /// Sets the smallest logical size the user can resize the window to, or removes the limit
+    /// with `None`. The initial setting comes from [`Config::min_window_size`].
+    pub fn set_min_window_size(&mut self, size: Option<LogicalSize<f64>>) {
+        self.internal.set_min_window_size(size);
+    }
+
+    /// Sets the largest logical size the user can resize the window to, or removes the limit with
+    /// `None`. The initial setting comes from [`Config::max_window_size`].
+    pub fn set_max_window_size(&mut self, size: Option<LogicalSize<f64>>) {
+        self.internal.set_max_window_size(size);
+    }
+
+    /// Returns the monitor that the window is currently on, or `None` if it can't be determined.
+    ///
+    /// This is queried every time it is called, so it stays correct when the user drags the window
+    /// to another display.
+    pub fn current_monitor(&self) -> Option<MonitorHandle> {
+        self.internal.current_monitor()
+    }
+
+    /// Moves the window so that its top left corner (including decorations) is at `position`, in
+    /// physical pixels on the desktop. The initial position comes from [`Config::window_position`].
+    ///
+    /// Some platforms, such as Wayland, don't let windows choose their position, in which case this
+    /// does nothing.
+    pub fn set_outer_position(&mut self, position: PhysicalPosition<i32>) {
+        self.internal.set_outer_position(position);
+    }
+
+    /// Moves the window to the top left corner of the given monitor. Use
+    /// [`available_monitors`][glutin::event_loop::EventLoopWindowTarget::available_monitors] on
+    /// your event loop to find the monitors you can choose from.
+    pub fn set_current_monitor(&mut self, monitor: &MonitorHandle) {
+        self.internal.set_current_monitor(monitor);
+    }
+
+    /// Changes the icon shown in the window's titlebar and taskbar entry, or resets it to the
+    /// platform default if `icon` is `None`. The initial icon comes from [`Config::window_icon`].
+    ///
+    /// Returns [`MglfbError::BadIcon`] if the icon's pixel data doesn't match its size. Some
+    /// platforms (for instance macOS) don't support window icons, in which case this does nothing.
+    pub fn set_window_icon(&mut self, icon: Option<WindowIcon>) -> Result<(), MglfbError> {
+        self.internal.set_window_icon(icon)
+    }
+
+    /// Returns `true` if the window is currently fullscreen.
+    pub fn is_fullscreen(&self) -> bool {
+        self.internal.is_fullscreen()
+    }
+
+    /// Makes the window borderless fullscreen on the monitor it is currently on, or puts it back
+    /// into a normal window. The initial setting comes from [`Config::fullscreen`].
+    ///
+    /// The viewport is resized to the new window size afterwards, so the buffer keeps filling the
+    /// window. Some platforms only finish switching after a few events, so if you are handling
+    /// events yourself you should still call `resize_viewport` when the window is resized.
+    pub fn set_fullscreen(&mut self, fullscreen: bool) {
+        self.internal.set_fullscreen(fullscreen);
+    }
+
+    /// Switches between fullscreen and windowed mode, which is what a fullscreen key (usually F11)
+    /// normally does. See [`set_fullscreen`][MiniGlFb::set_fullscreen].
+    pub fn toggle_fullscreen(&mut self) {
+        self.internal.toggle_fullscreen();
+    }
+
+    /// Returns the refresh rate of the monitor the window is currently on, in hertz, or `None` if
+    /// it can't be determined.
+    ///
+    /// Since the windowing system does not report which video mode is currently active, this is the
+    /// highest refresh rate available at the monitor's current resolution. This is useful for
+    /// adapting your frame pacing when the window is moved between, say, a 60Hz and a 144Hz
+    /// display.
+    pub fn current_refresh_rate(&self) -> Option<f32> {
+        self.internal.current_refresh_rate()
+    }
+
+    /// Locks the window to an aspect ratio (width / height), or unlocks it if `None` is passed.
+    ///
+    /// While the aspect ratio is locked, whenever the window is resized by `persist` or
+    /// `glutin_handle_basic_input`, it is immediately resized again to the closest size that
+    /// matches the ratio, so the buffer always fills the entire window without being stretched.
+    /// To lock the window to the shape of the buffer, pass the buffer's width divided by its
+    /// height. The window is asked to resize right away if it doesn't already match; the viewport
+    /// only changes once the window actually reports its new size.
+    ///
+    /// This relies on the window manager honoring programmatic resizes. Some don't (tiling window
+    /// managers, for example, or some Wayland compositors); on those the viewport still follows
+    /// the window, so the buffer will be stretched as usual. Since the correction happens after the
+    /// user has already resized the window, the window may also briefly flicker to the wrong size
+    /// while it is being dragged.
+    pub fn lock_aspect_ratio(&mut self, aspect_ratio: Option<f64>) {
+        self.internal.lock_aspect_ratio(aspect_ratio);
+    }
+
+    /// Makes the buffer follow the size of the window, so that one pixel of the buffer is always
+    /// one logical pixel of the window, or stops it with `false`. The buffer is resized right away
+    /// when this is turned on, and then whenever `persist` or `glutin_handle_basic_input` sees the
+    /// window being resized (which [`BasicInput::resized`] tells you about).
+    ///
+    /// Like after [`resize_buffer`][MiniGlFb::resize_buffer], the next buffer you upload has to
+    /// have the new size, so check [`buffer_size`][Framebuffer::buffer_size] each frame instead of
+    /// keeping your own. Until then the old contents are shown stretched. Each resize reallocates
+    /// the buffer's texture on the next upload, so dragging the edge of a big window is slower than
+    /// with a fixed buffer.
+    ///
+    /// ```rust,no_run
+    /// use mini_gl_fb::{get_fancy, config};
+    /// use mini_gl_fb::glutin::event_loop::EventLoop;
+    /// use mini_gl_fb::glutin::event::VirtualKeyCode;
+    ///
+    /// let mut event_loop = EventLoop::new();
+    /// let mut fb = get_fancy(config! { resizable: true, buffer_follows_window: true }, &event_loop);
+    ///
+    /// fb.glutin_handle_basic_input(&mut event_loop, |fb, input| {
+    ///     input.wait = true;
+    ///     let (width, height) = fb.buffer_size();
+    ///     fb.update_buffer(&vec![[64u8, 128, 192, 255]; (width * height) as usize]);
+    ///     !input.key_is_down(VirtualKeyCode::Escape)
+    /// });
+    /// ```
+    pub fn set_buffer_follows_window(&mut self, follow: bool) {
+        self.internal.set_buffer_follows_window(follow);
+    }
+
+    /// Blocks until the next vertical retrace of the display the window is on.
+    ///
+    /// This is meant for aligning work to the display (for frame accurate capture, or measuring
+    /// latency) without relying on the implicit wait in a vsynced buffer swap. Returns `true` if
+    /// it actually waited for the retrace.
+    ///
+    /// Currently that is only possible on X11 with the `GLX_SGI_video_sync` extension, which most
+    /// drivers provide. Everywhere else this falls back to sleeping for one refresh period (based
+    /// on [`current_refresh_rate`][MiniGlFb::current_refresh_rate], or 60Hz if that is unknown)
+    /// and returns `false`. The fallback keeps loops that call this every frame running at about
+    /// the right rate, but it is not aligned to the retrace in any way.
+    pub fn wait_for_vblank(&self) -> bool {
+        self.internal.wait_for_vblank()
+    }
+
+    /// Turns vsync on or off after the window has been created. The initial setting comes from
+    /// [`Config::vsync`].
+    ///
+    /// With vsync on, every buffer swap (so every [`update_buffer`][MiniGlFb::update_buffer] or
+    /// [`redraw`][MiniGlFb::redraw]) blocks until the display's next vertical retrace. That makes
+    /// it the natural way to cap the framerate of a
+    /// [`glutin_handle_basic_input`][MiniGlFb::glutin_handle_basic_input] loop with `wait` set to
+    /// `false`: the loop runs with [`ControlFlow::Poll`][glutin::event_loop::ControlFlow::Poll],
+    /// so without vsync it redraws as fast as the GPU allows and keeps a CPU core busy. Note that
+    /// vsync only throttles frames that actually swap buffers, so a callback that doesn't draw
+    /// anything will still spin.
+    ///
+    /// Glutin can only choose vsync at context creation, so this goes to the platform directly:
+    /// `eglSwapInterval` on EGL, `GLX_EXT_swap_control` or `GLX_MESA_swap_control` on X11 and
+    /// `WGL_EXT_swap_control` on Windows. Returns [`MglfbError::VsyncUnsupported`] if none of those
+    /// are available, which is always the case on macOS.
+    pub fn set_vsync(&mut self, vsync: bool) -> Result<(), MglfbError> {
+        self.internal.set_vsync(vsync)
+    }
+
+    /// Sets the swap interval directly, for more control than [`set_vsync`][MiniGlFb::set_vsync]
+    /// gives: 0 turns vsync off, 1 waits for every vertical blank, `n` waits for `n` of them (so 2
+    /// halves the refresh rate), and -1 is adaptive vsync, which syncs like 1 but swaps right
+    /// away when a frame comes in late instead of waiting a whole refresh.
+    ///
+    /// This goes through the same platform functions as `set_vsync`. Adaptive vsync needs
+    /// `GLX_EXT_swap_control_tear` or `WGL_EXT_swap_control_tear`, and isn't available on EGL.
+    /// Returns [`MglfbError::VsyncUnsupported`] if the interval can't be set; nothing changes in
+    /// that case, so you can fall back to 1.
+    ///
+    /// ```rust
+    /// # let (_, mut fb) = mini_gl_fb::gotta_go_fast("Hello world!", 800.0, 600.0);
+    /// if fb.set_swap_interval(-1).is_err() {
+    ///     let _ = fb.set_swap_interval(1);
+    /// }
+    /// ```
+    pub fn set_swap_interval(&mut self, interval: i32) -> Result<(), MglfbError> {
+        self.internal.set_swap_interval(interval)
+    }
+
     /// Keeps the window open until the user closes it.
     ///
-    /// Supports pressing escape to quit. Automatically scales the rendered buffer to the size of
-    /// the window if the window is resiable (but this does not resize the buffer).
+    /// Supports pressing escape to quit (see [`set_quit_key`][MiniGlFb::set_quit_key] to change
+    /// that). Automatically scales the rendered buffer to the size of the window if the window is
+    /// resiable (but this does not resize the buffer).
     pub fn persist<ET: 'static>(&mut self, event_loop: &mut EventLoop<ET>) {
         self.internal.persist(event_loop);
     }
 
+    /// The same as [`persist`][MiniGlFb::persist], but also redraws the buffer `fps` times per
+    /// second. Useful for animated shaders, which use `u_time` and so need redrawing even when
+    /// the buffer doesn't change.
+    ///
+    /// Instead of redrawing as fast as possible like
+    /// [`persist_and_redraw`][MiniGlFb::persist_and_redraw], this sleeps until the next frame is
+    /// due using [`ControlFlow::WaitUntil`][glutin::event_loop::ControlFlow::WaitUntil], so it
+    /// leaves the CPU idle in between. Resizing and closing the window are still handled right
+    /// away. Frames that are missed (for instance because the system was busy) are skipped rather
+    /// than drawn late.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `fps` is not positive.
+    pub fn persist_with_framerate<ET: 'static>(&mut self, event_loop: &mut EventLoop<ET>, fps: f64) {
+        self.internal.persist_with_framerate(event_loop, fps);
+    }
+
+    /// The same as [`persist_with_framerate`][MiniGlFb::persist_with_framerate], but calls
+    /// `frame` every `interval` to change the buffer before it is redrawn and presented. This is
+    /// the simplest way to animate something that doesn't need input.
+    ///
+    /// The loop sleeps in between frames, and frames that are missed are skipped rather than
+    /// drawn late, so don't count calls to measure time; use an [`Instant`][std::time::Instant]
+    /// instead.
+    ///
+    /// ```rust,no_run
+    /// use std::time::Duration;
+    ///
+    /// let (mut event_loop, mut fb) = mini_gl_fb::gotta_go_fast("Hello world!", 256.0, 1.0);
+    /// let mut shade = 0u8;
+    ///
+    /// fb.persist_animated(&mut event_loop, Duration::from_millis(16), |fb| {
+    ///     shade = shade.wrapping_add(1);
+    ///     fb.update_buffer(&vec![[shade, 0, 0, 255]; 256]);
+    /// });
+    /// ```
+    pub fn persist_animated<ET: 'static, F: FnMut(&mut Framebuffer)>(
+        &mut self, event_loop: &mut EventLoop<ET>, interval: Duration, frame: F
+    ) {
+        self.internal.persist_animated(event_loop, interval, frame);
+    }
+
+    /// The same as [`persist`][MiniGlFb::persist], but also returns as soon as `done` returns
+    /// `true`, so that the window can be driven without anyone there to close it, like in tests
+    /// and scripted demos.
+    ///
+    /// `done` is called once per iteration of the event loop, and while it returns `false` the
+    /// buffer is redrawn and presented. The loop doesn't wait for events in between, so unless
+    /// [vsync][MiniGlFb::set_vsync] is on it runs as fast as it can. Closing the window or pressing
+    /// the [quit key][MiniGlFb::set_quit_key] still returns right away. The window stays open
+    /// afterwards, so you can take a [`screenshot`][MiniGlFb::screenshot] or keep going.
+    ///
+    /// ```rust,no_run
+    /// use std::time::{Duration, Instant};
+    ///
+    /// let (mut event_loop, mut fb) = mini_gl_fb::gotta_go_fast("Hello world!", 800.0, 600.0);
+    /// fb.update_buffer(&vec![[128u8, 0, 0, 255]; 800 * 600]);
+    ///
+    /// // show the window for two seconds
+    /// let start = Instant::now();
+    /// fb.persist_until(&mut event_loop, || start.elapsed() > Duration::from_secs(2));
+    /// ```
+    pub fn persist_until<ET: 'static, F: FnMut() -> bool>(
+        &mut self, event_loop: &mut EventLoop<ET>, done: F
+    ) {
+        self.internal.persist_until(event_loop, done);
+    }
+
+    /// The same as [`persist_until`][MiniGlFb::persist_until], but returns after presenting
+    /// `frames` frames.
+    ///
+    /// ```rust,no_run
+    /// let (mut event_loop, mut fb) = mini_gl_fb::gotta_go_fast("Hello world!", 800.0, 600.0);
+    /// fb.update_buffer(&vec![[128u8, 0, 0, 255]; 800 * 600]);
+    ///
+    /// fb.persist_frames(&mut event_loop, 3);
+    /// let pixels = fb.screenshot(false);
+    /// assert_eq!(&pixels[..4], &[128, 0, 0, 255]);
+    /// ```
+    pub fn persist_frames<ET: 'static>(&mut self, event_loop: &mut EventLoop<ET>, frames: u32) {
+        self.internal.persist_frames(event_loop, frames);
+    }
+
+    /// Changes the key that closes the window in [`persist`][MiniGlFb::persist], or disables the
+    /// shortcut with `None`. The default is [`VirtualKeyCode::Escape`].
+    ///
+    /// The window can always be closed normally. This has no effect on
+    /// [`glutin_handle_basic_input`][MiniGlFb::glutin_handle_basic_input], where your handler
+    /// decides when to quit.
+    ///
+    /// ```rust
+    /// # use mini_gl_fb::get_fancy;
+    /// # use mini_gl_fb::glutin::event_loop::EventLoop;
+    /// use mini_gl_fb::glutin::event::VirtualKeyCode;
+    /// # let mut fb = get_fancy(Default::default(), &EventLoop::new());
+    ///
+    /// fb.set_quit_key(Some(VirtualKeyCode::Q));
+    /// ```
+    pub fn set_quit_key(&mut self, key: Option<VirtualKeyCode>) {
+        self.internal.set_quit_key(key);
+    }
+
+    /// Lets you intercept the user closing the window (with the close button, Alt+F4 and so on),
+    /// for instance to ask about unsaved changes. `handler` is called whenever that happens in
+    /// [`persist`][MiniGlFb::persist] and the other `persist_*` loops, or
+    /// [`glutin_handle_basic_input`][MiniGlFb::glutin_handle_basic_input]; the window only closes
+    /// if it returns `true`. If it returns `false` the loop keeps going as if nothing happened.
+    ///
+    /// Without a handler, the window always closes. This doesn't affect the
+    /// [quit key][MiniGlFb::set_quit_key], or your basic input handler returning `false`.
+    ///
+    /// ```rust
+    /// # use mini_gl_fb::get_fancy;
+    /// # use mini_gl_fb::glutin::event_loop::EventLoop;
+    /// use std::cell::Cell;
+    /// use std::rc::Rc;
+    /// # let mut fb = get_fancy(Default::default(), &EventLoop::new());
+    ///
+    /// let unsaved = Rc::new(Cell::new(true));
+    /// let unsaved_on_close = unsaved.clone();
+    /// fb.set_close_handler(move || {
+    ///     if unsaved_on_close.get() {
+    ///         eprintln!("There are unsaved changes, press S to save first");
+    ///         return false;
+    ///     }
+    ///     true
+    /// });
+    /// ```
+    pub fn set_close_handler<F: FnMut() -> bool + 'static>(&mut self, handler: F) {
+        self.internal.set_close_handler(handler);
+    }
+
     /// `persist` implementation.
     ///
     /// When redraw is true, redraws as fast as possible. This function is primarily for debugging.
@@ -352,12 +1546,64 @@ impl MiniGlFb {
     /// You can cause the handler to exit by returning false from it. This does not kill the
     /// window, so as long as you still have it in scope, you can actually keep using it and,
     /// for example, resume handling input but with a different handler callback.
+    ///
+    /// If you only want to step away for a moment (for instance, to show a native file dialog),
+    /// set [`BasicInput::suspend`] to `true` from your handler instead. This method will return
+    /// as soon as your handler does, and the next call to `glutin_handle_basic_input` carries on
+    /// where it left off, with your scheduled wakeups still intact. Keys and mouse buttons are
+    /// considered released when handling resumes, since they may have been released while no one
+    /// was listening.
     pub fn glutin_handle_basic_input<ET: 'static, F: FnMut(&mut Framebuffer, &mut BasicInput) -> bool>(
         &mut self, event_loop: &mut EventLoop<ET>, handler: F
     ) {
         self.internal.glutin_handle_basic_input(event_loop, handler);
     }
 
+    /// Like [`glutin_handle_basic_input`][MiniGlFb::glutin_handle_basic_input], but drawing is
+    /// separate from input handling: `redraw` is called `fps` times per second to draw a frame,
+    /// and `handler` only when the input changes (as if [`BasicInput::wait`] were set, which it
+    /// is at first). Buffers are swapped for you after either of them draws.
+    ///
+    /// This suits apps that animate continuously but only need to react to input now and then,
+    /// without scheduling a [`Wakeup`][breakout::Wakeup] for every frame.
+    ///
+    /// ```rust,no_run
+    /// # let (mut event_loop, mut fb) = mini_gl_fb::gotta_go_fast("Hello world!", 800.0, 600.0);
+    /// use mini_gl_fb::glutin::event::VirtualKeyCode;
+    /// use std::cell::Cell;
+    ///
+    /// let mut brightness = 0u8;
+    /// // both closures need it, so it can't be borrowed mutably by one of them
+    /// let paused = Cell::new(false);
+    /// let mut buffer = vec![[0u8, 0, 0, 255]; 800 * 600];
+    ///
+    /// fb.glutin_handle_basic_input_with_redraw(&mut event_loop, 60.0, |_, input| {
+    ///     if input.key_pressed(VirtualKeyCode::Space) {
+    ///         paused.set(!paused.get());
+    ///     }
+    ///     !input.key_is_down(VirtualKeyCode::Escape)
+    /// }, |fb| {
+    ///     if !paused.get() {
+    ///         brightness = brightness.wrapping_add(1);
+    ///         buffer.iter_mut().for_each(|pixel| pixel[0] = brightness);
+    ///     }
+    ///     fb.update_buffer(&buffer);
+    /// });
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if `fps` is not positive.
+    pub fn glutin_handle_basic_input_with_redraw<ET, F, R>(
+        &mut self, event_loop: &mut EventLoop<ET>, fps: f64, handler: F, redraw: R
+    ) where
+        ET: 'static,
+        F: FnMut(&mut Framebuffer, &mut BasicInput) -> bool,
+        R: FnMut(&mut Framebuffer),
+    {
+        self.internal.glutin_handle_basic_input_with_redraw(event_loop, fps, handler, redraw);
+    }
+
     /// Need full access to Glutin's event handling? No problem!
     ///
     /// Hands you the window we created, so you can handle events however you want, and the
@@ -366,7 +1612,99 @@ impl MiniGlFb {
     /// **IMPORTANT:** You should make sure to render something before swapping buffers or **the
     /// window may flash violently**. You can call `fb.redraw()` directly before if you are unsure
     /// that an OpenGL draw call was issued. `fb.update_buffer` will typically issue a draw call.
+    /// Until the first buffer is uploaded, `fb.redraw()` fills the window with
+    /// [`Config::background_color`].
     pub fn glutin_breakout(self) -> GlutinBreakout {
         self.internal.glutin_breakout()
     }
+
+    /// Like [`glutin_breakout`][MiniGlFb::glutin_breakout], but only borrows the context and the
+    /// framebuffer, so you can do something low-level once and then keep using `MiniGlFb`.
+    ///
+    /// The same warning about swapping buffers applies. The context can't be replaced through
+    /// this, only used; anything else you change on the window or framebuffer stays changed.
+    ///
+    /// ```rust
+    /// # let (_, mut fb) = mini_gl_fb::gotta_go_fast("Hello world!", 800.0, 600.0);
+    /// let (context, framebuffer) = fb.breakout_ref();
+    /// context.window().set_title("Renamed");
+    /// framebuffer.redraw();
+    /// context.swap_buffers().unwrap();
+    ///
+    /// // and back to the high-level API
+    /// fb.update_buffer(&vec![[128u8, 0, 0, 255]; 800 * 600]);
+    /// ```
+    pub fn breakout_ref(&mut self) -> (&WindowedContext<PossiblyCurrent>, &mut Framebuffer) {
+        self.internal.breakout_ref()
+    }
+}
+
+/// Chainable versions of the setup methods, for configuring a window right after creating it:
+///
+/// ```rust
+/// use mini_gl_fb::{BufferFormat, ScaleMode};
+/// # let (_, mut fb) = mini_gl_fb::gotta_go_fast("Hello world!", 800.0, 600.0);
+///
+/// fb.with_buffer_format::<u8>(BufferFormat::R)
+///     .with_grayscale_shader()
+///     .with_scale_mode(ScaleMode::AspectFit)
+///     .with_letterbox_color([0.1, 0.1, 0.1, 1.0]);
+/// ```
+///
+/// Each one does exactly what the method it's named after does. Anything that can fail (like
+/// compiling a shader) is left out, so that errors aren't lost in the chain.
+impl MiniGlFb {
+    /// Chainable version of [`change_buffer_format`][MiniGlFb::change_buffer_format].
+    pub fn with_buffer_format<T: ToGlType>(&mut self, format: BufferFormat) -> &mut Self {
+        self.change_buffer_format::<T>(format);
+        self
+    }
+
+    /// Chainable version of [`use_grayscale_shader`][MiniGlFb::use_grayscale_shader].
+    pub fn with_grayscale_shader(&mut self) -> &mut Self {
+        self.use_grayscale_shader();
+        self
+    }
+
+    /// Chainable version of [`use_luminance_shader`][MiniGlFb::use_luminance_shader].
+    pub fn with_luminance_shader(&mut self) -> &mut Self {
+        self.use_luminance_shader();
+        self
+    }
+
+    /// Chainable version of [`use_builtin_shader`][MiniGlFb::use_builtin_shader].
+    pub fn with_builtin_shader(&mut self, shader: BuiltinShader) -> &mut Self {
+        self.use_builtin_shader(shader);
+        self
+    }
+
+    /// Chainable version of [`set_scale_mode`][MiniGlFb::set_scale_mode].
+    pub fn with_scale_mode(&mut self, scale_mode: ScaleMode) -> &mut Self {
+        self.set_scale_mode(scale_mode);
+        self
+    }
+
+    /// Chainable version of [`set_letterbox_color`][MiniGlFb::set_letterbox_color].
+    pub fn with_letterbox_color(&mut self, color: [f32; 4]) -> &mut Self {
+        self.set_letterbox_color(color);
+        self
+    }
+
+    /// Chainable version of [`enable_mipmaps`][MiniGlFb::enable_mipmaps].
+    pub fn with_mipmaps(&mut self, enable: bool) -> &mut Self {
+        self.enable_mipmaps(enable);
+        self
+    }
+
+    /// Chainable version of [`set_quit_key`][MiniGlFb::set_quit_key].
+    pub fn with_quit_key(&mut self, key: Option<VirtualKeyCode>) -> &mut Self {
+        self.set_quit_key(key);
+        self
+    }
+
+    /// Chainable version of [`set_buffer_follows_window`][MiniGlFb::set_buffer_follows_window].
+    pub fn with_buffer_follows_window(&mut self, follow: bool) -> &mut Self {
+        self.set_buffer_follows_window(follow);
+        self
+    }
 }
@@ -0,0 +1,14 @@
+#version 330 core
+
+in vec2 v_uv;
+
+out vec4 frag_color;
+
+uniform sampler2D u_buffer;
+
+void main() {
+    vec4 color = texture(u_buffer, v_uv);
+    // Rec. 709 luma coefficients
+    float luminance = dot(color.rgb, vec3(0.2126, 0.7152, 0.0722));
+    frag_color = vec4(vec3(luminance), color.a);
+}
@@ -0,0 +1,14 @@
+#version 330 core
+
+in vec2 v_uv;
+
+out vec4 frag_color;
+
+uniform sampler2D u_buffer;
+uniform sampler2D u_palette;
+
+void main() {
+    // the index comes back normalized to [0, 1], so turn it back into a texel coordinate
+    int index = int(texture(u_buffer, v_uv).r * 255.0 + 0.5);
+    frag_color = texelFetch(u_palette, ivec2(index, 0), 0);
+}
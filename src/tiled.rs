@@ -0,0 +1,138 @@
+//! Contains the [`TiledView`] struct, which draws several independent [`Framebuffer`]s side by
+//! side in a single window.
+
+use crate::core::Framebuffer;
+
+use glutin::{WindowedContext, PossiblyCurrent, ContextError};
+use glutin::dpi::PhysicalSize;
+
+/// Divides a window into a grid of tiles, each showing its own [`Framebuffer`] with its own buffer,
+/// format and shaders.
+///
+/// This is handy for comparing several variants of a shader side by side, or for debug views that
+/// show several stages of a pipeline at once. Tiles are laid out left to right, top to bottom, and
+/// all of them are drawn and presented together.
+///
+/// All the framebuffers must belong to the same OpenGL context (the one of the window they are
+/// shown in). The easiest way to get them is to make the window's context current and call
+/// [`init_framebuffer`][crate::core::init_framebuffer] for each tile.
+///
+/// ```
+/// use mini_gl_fb::{get_fancy, config, BuiltinShader};
+/// use mini_gl_fb::core::init_framebuffer;
+/// use mini_gl_fb::tiled::TiledView;
+/// use mini_gl_fb::glutin::event_loop::EventLoop;
+///
+/// let event_loop = EventLoop::new();
+/// let breakout = get_fancy(config! {}, &event_loop).glutin_breakout();
+/// let size = breakout.context.window().inner_size();
+///
+/// let mut view = TiledView::new(2);
+/// view.push(breakout.fb);
+/// view.push(init_framebuffer(2, 2, size.width, size.height, true));
+/// view.resize_viewport(size.width, size.height);
+///
+/// let buffer = [[255u8, 0, 0, 255], [0, 255, 0, 255], [0, 0, 255, 255], [255, 255, 255, 255]];
+/// view.tiles[1].use_builtin_shader(BuiltinShader::Luminance);
+/// for tile in &mut view.tiles {
+///     tile.resize_buffer(2, 2);
+///     tile.update_buffer(&buffer);
+/// }
+///
+/// view.present(&breakout.context).unwrap();
+/// ```
+#[non_exhaustive]
+#[derive(Debug)]
+pub struct TiledView {
+    /// The framebuffers being shown, in the order they are laid out.
+    pub tiles: Vec<Framebuffer>,
+    /// How many tiles are shown next to each other before wrapping to the next row. Never 0; see
+    /// [`set_columns`][TiledView::set_columns].
+    columns: u32,
+    /// The physical size of the whole area the tiles are laid out in, usually the size of the
+    /// window.
+    pub vp_size: PhysicalSize<u32>,
+}
+
+impl TiledView {
+    /// Creates an empty view with the given number of columns.
+    pub fn new(columns: u32) -> Self {
+        TiledView {
+            tiles: vec![],
+            columns: columns.max(1),
+            vp_size: PhysicalSize::new(0, 0),
+        }
+    }
+
+    /// Adds a framebuffer as the last tile, and lays out the tiles again.
+    pub fn push(&mut self, fb: Framebuffer) {
+        self.tiles.push(fb);
+        self.layout();
+    }
+
+    /// How many tiles are shown next to each other before wrapping to the next row.
+    pub fn columns(&self) -> u32 {
+        self.columns
+    }
+
+    /// Changes how many tiles are shown next to each other, and lays out the tiles again. There is
+    /// always at least one column, so 0 is treated as 1.
+    pub fn set_columns(&mut self, columns: u32) {
+        self.columns = columns.max(1);
+        self.layout();
+    }
+
+    /// The number of rows needed to show all of the tiles.
+    pub fn rows(&self) -> u32 {
+        match self.tiles.len() as u32 {
+            0 => 0,
+            // a partly filled last row still counts
+            tiles => (tiles - 1) / self.columns + 1,
+        }
+    }
+
+    /// Sets the physical size of the area the tiles are laid out in. Call this whenever the window
+    /// is resized, like you would call [`Framebuffer::resize_viewport`] for a single framebuffer.
+    pub fn resize_viewport(&mut self, width: u32, height: u32) {
+        self.vp_size = PhysicalSize::new(width, height);
+        self.layout();
+    }
+
+    /// Draws every tile to the current context, without presenting them.
+    pub fn draw(&mut self) {
+        unsafe {
+            gl::Viewport(0, 0, self.vp_size.width as _, self.vp_size.height as _);
+            gl::ClearColor(0.0, 0.0, 0.0, 1.0);
+            gl::Clear(gl::COLOR_BUFFER_BIT);
+        }
+        for tile in &mut self.tiles {
+            tile.redraw();
+        }
+    }
+
+    /// Draws every tile and presents them all at once.
+    pub fn present(&mut self, context: &WindowedContext<PossiblyCurrent>) -> Result<(), ContextError> {
+        self.draw();
+        context.swap_buffers()
+    }
+
+    fn layout(&mut self) {
+        let columns = self.columns;
+        let rows = self.rows().max(1);
+        let PhysicalSize { width, height } = self.vp_size;
+
+        for (index, tile) in self.tiles.iter_mut().enumerate() {
+            let column = index as u32 % columns;
+            let row = index as u32 / columns;
+
+            // computing both edges of each tile makes sure rounding never leaves a gap between them
+            let left = column * width / columns;
+            let right = (column + 1) * width / columns;
+            // rows go from the top down, but OpenGL puts the origin in the bottom left
+            let top = height - row * height / rows;
+            let bottom = height - (row + 1) * height / rows;
+
+            tile.set_viewport_rect(left as i32, bottom as i32, right - left, top - bottom);
+        }
+    }
+}
@@ -0,0 +1,212 @@
+//! Platform specific ways of synchronizing with the display, for the things glutin doesn't
+//! expose itself.
+
+use glutin::{WindowedContext, PossiblyCurrent};
+
+#[allow(unused_imports)]
+use std::os::raw::{c_char, c_int, c_uint, c_ulong, c_void};
+
+/// Loads a function through the context, returning `None` if it isn't there.
+#[allow(dead_code)]
+unsafe fn load<F: Copy>(context: &WindowedContext<PossiblyCurrent>, name: &str) -> Option<F> {
+    let ptr = context.get_proc_address(name);
+    if ptr.is_null() {
+        None
+    } else {
+        Some(std::mem::transmute_copy(&ptr))
+    }
+}
+
+/// Sets the swap interval through `eglSwapInterval`, if glutin is using EGL.
+#[cfg(any(
+    target_os = "linux",
+    target_os = "dragonfly",
+    target_os = "freebsd",
+    target_os = "netbsd",
+    target_os = "openbsd",
+    target_os = "windows",
+))]
+unsafe fn egl_swap_interval(context: &WindowedContext<PossiblyCurrent>, interval: i32) -> Option<bool> {
+    use glutin::platform::ContextTraitExt;
+
+    type SwapInterval = unsafe extern "C" fn(*const c_void, c_int) -> c_uint;
+
+    let display = context.get_egl_display()?;
+    if interval < 0 {
+        // EGL has no adaptive vsync, and would quietly clamp the interval to its minimum
+        return Some(false);
+    }
+    let swap_interval: SwapInterval = match load(context, "eglSwapInterval") {
+        Some(swap_interval) => swap_interval,
+        None => return Some(false),
+    };
+    Some(swap_interval(display, interval) != 0)
+}
+
+#[cfg(any(
+    target_os = "linux",
+    target_os = "dragonfly",
+    target_os = "freebsd",
+    target_os = "netbsd",
+    target_os = "openbsd",
+))]
+mod glx {
+    use super::*;
+
+    use glutin::platform::unix::WindowExtUnix;
+    use std::ffi::CStr;
+
+    type QueryExtensionsString = unsafe extern "C" fn(*mut c_void, c_int) -> *const c_char;
+
+    /// Checks if the GLX implementation behind this window supports an extension. Returns `false`
+    /// if the window isn't on X11 or glutin is using EGL instead of GLX.
+    ///
+    /// glXGetProcAddress happily returns pointers for functions that don't exist, so asking for
+    /// the extension string is the only safe way to find out if a function can be called.
+    pub unsafe fn supports(context: &WindowedContext<PossiblyCurrent>, extension: &str) -> bool {
+        use glutin::platform::ContextTraitExt;
+
+        let window = context.window();
+        let (display, screen) = match (window.xlib_display(), window.xlib_screen_id()) {
+            (Some(display), Some(screen)) => (display, screen),
+            // not running on X11 at all
+            _ => return false,
+        };
+
+        if context.get_egl_display().is_some() {
+            // glutin fell back to EGL, so there are no GLX functions to be had
+            return false;
+        }
+
+        let query: QueryExtensionsString = match load(context, "glXQueryExtensionsString") {
+            Some(query) => query,
+            None => return false,
+        };
+        let extensions = query(display, screen);
+        !extensions.is_null() && CStr::from_ptr(extensions)
+            .to_string_lossy()
+            .split(' ')
+            .any(|supported| supported == extension)
+    }
+}
+
+/// Blocks until the next vertical retrace using `GLX_SGI_video_sync`. Returns `false` without
+/// waiting if the extension isn't available.
+#[cfg(any(
+    target_os = "linux",
+    target_os = "dragonfly",
+    target_os = "freebsd",
+    target_os = "netbsd",
+    target_os = "openbsd",
+))]
+pub(crate) fn wait_for_vblank(context: &WindowedContext<PossiblyCurrent>) -> bool {
+    type GetVideoSync = unsafe extern "C" fn(*mut c_uint) -> c_int;
+    type WaitVideoSync = unsafe extern "C" fn(c_int, c_int, *mut c_uint) -> c_int;
+
+    unsafe {
+        if !glx::supports(context, "GLX_SGI_video_sync") {
+            return false;
+        }
+
+        let get_video_sync: Option<GetVideoSync> = load(context, "glXGetVideoSyncSGI");
+        let wait_video_sync: Option<WaitVideoSync> = load(context, "glXWaitVideoSyncSGI");
+        let (get_video_sync, wait_video_sync) = match (get_video_sync, wait_video_sync) {
+            (Some(get), Some(wait)) => (get, wait),
+            _ => return false,
+        };
+
+        let mut count = 0;
+        if get_video_sync(&mut count) != 0 {
+            return false;
+        }
+        // wait until the counter changes, i.e. the next retrace
+        wait_video_sync(2, ((count + 1) % 2) as c_int, &mut count) == 0
+    }
+}
+
+#[cfg(not(any(
+    target_os = "linux",
+    target_os = "dragonfly",
+    target_os = "freebsd",
+    target_os = "netbsd",
+    target_os = "openbsd",
+)))]
+pub(crate) fn wait_for_vblank(_context: &WindowedContext<PossiblyCurrent>) -> bool {
+    false
+}
+
+/// Sets the swap interval of the current context: 0 disables vsync, 1 syncs every buffer swap to
+/// the display, and negative intervals are adaptive vsync. Returns `false` if the platform doesn't
+/// let us change it, or doesn't support the interval.
+#[cfg(any(
+    target_os = "linux",
+    target_os = "dragonfly",
+    target_os = "freebsd",
+    target_os = "netbsd",
+    target_os = "openbsd",
+))]
+pub(crate) fn set_swap_interval(context: &WindowedContext<PossiblyCurrent>, interval: i32) -> bool {
+    use glutin::platform::unix::WindowExtUnix;
+
+    type SwapIntervalExt = unsafe extern "C" fn(*mut c_void, c_ulong, c_int);
+    type SwapIntervalMesa = unsafe extern "C" fn(c_uint) -> c_int;
+
+    unsafe {
+        if let Some(result) = egl_swap_interval(context, interval) {
+            return result;
+        }
+
+        let window = context.window();
+        // negative intervals are an X error without the tear extension, not just ignored
+        let supports_interval = interval >= 0
+            || glx::supports(context, "GLX_EXT_swap_control_tear");
+        if supports_interval && glx::supports(context, "GLX_EXT_swap_control") {
+            if let (Some(display), Some(drawable), Some(swap_interval)) = (
+                window.xlib_display(),
+                window.xlib_window(),
+                load::<SwapIntervalExt>(context, "glXSwapIntervalEXT"),
+            ) {
+                swap_interval(display, drawable, interval);
+                return true;
+            }
+        }
+
+        // GLX_SGI_swap_control can't turn vsync off, so this is the only other option
+        if interval >= 0 && glx::supports(context, "GLX_MESA_swap_control") {
+            if let Some(swap_interval) = load::<SwapIntervalMesa>(context, "glXSwapIntervalMESA") {
+                return swap_interval(interval as c_uint) == 0;
+            }
+        }
+
+        false
+    }
+}
+
+#[cfg(target_os = "windows")]
+pub(crate) fn set_swap_interval(context: &WindowedContext<PossiblyCurrent>, interval: i32) -> bool {
+    type SwapIntervalExt = unsafe extern "system" fn(c_int) -> c_int;
+
+    unsafe {
+        if let Some(result) = egl_swap_interval(context, interval) {
+            return result;
+        }
+
+        // fails by itself for negative intervals without WGL_EXT_swap_control_tear
+        match load::<SwapIntervalExt>(context, "wglSwapIntervalEXT") {
+            Some(swap_interval) => swap_interval(interval) != 0,
+            None => false,
+        }
+    }
+}
+
+#[cfg(not(any(
+    target_os = "linux",
+    target_os = "dragonfly",
+    target_os = "freebsd",
+    target_os = "netbsd",
+    target_os = "openbsd",
+    target_os = "windows",
+)))]
+pub(crate) fn set_swap_interval(_context: &WindowedContext<PossiblyCurrent>, _interval: i32) -> bool {
+    false
+}